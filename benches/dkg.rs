@@ -0,0 +1,277 @@
+//! Benchmarks of every phase of the protocol, as seen by a single member, for different
+//! committee sizes. The transcript of the protocol is generated once per committee size, and
+//! every phase transition of the last member is replayed over the recorded broadcast messages.
+//!
+//! In the misbehaving scenario, `threshold` members do not broadcast in phase 3, so that
+//! the honest members complain in phase 4, disclose their shares in phase 5, and reconstruct
+//! the shares of the misbehaving members in `finalise`.
+//!
+//! The transcripts of the larger committees take several minutes to generate. They are only
+//! generated for the selected benchmarks, so a single size can be run with a filter, e.g.
+//! `cargo bench --features bench --bench dkg -- /64$`. The `bench` feature is required to
+//! clone the member states.
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use rand_core::OsRng;
+use std::cell::RefCell;
+use std::rc::Rc;
+use DKG::dkg::broadcast::{
+    BroadcastPhase1, BroadcastPhase2, BroadcastPhase3, BroadcastPhase4, BroadcastPhase5,
+};
+use DKG::dkg::committee::{
+    DistributedKeyGeneration, Environment, Phase1, Phase2, Phase3, Phase4, Phase5, Phases,
+};
+use DKG::dkg::procedure_keys::{MemberCommunicationKey, MemberCommunicationPublicKey};
+
+type Group = RistrettoPoint;
+
+const COMMITTEE_SIZES: [usize; 6] = [4, 16, 64, 128, 256, 512];
+
+/// Recorded run of the protocol. It contains the state of the last member before every phase
+/// transition, and the broadcast messages of all members in every phase.
+struct Transcript {
+    environment: Environment<Group>,
+    keys: Vec<MemberCommunicationKey<Group>>,
+    pks: Vec<MemberCommunicationPublicKey<Group>>,
+    phase_1: Phases<Group, Phase1>,
+    broadcasts_1: Vec<Option<BroadcastPhase1<Group>>>,
+    phase_2: Phases<Group, Phase2>,
+    broadcasts_2: Vec<Option<BroadcastPhase2<Group>>>,
+    phase_3: Phases<Group, Phase3>,
+    broadcasts_3: Vec<Option<BroadcastPhase3<Group>>>,
+    phase_4: Phases<Group, Phase4>,
+    broadcasts_4: Vec<Option<BroadcastPhase4<Group>>>,
+    phase_5: Phases<Group, Phase5>,
+    broadcasts_5: Vec<Option<BroadcastPhase5<Group>>>,
+}
+
+// Messages of all members but `index`, ordered by sender.
+fn others<T: Clone>(messages: &[Option<T>], index: usize) -> Vec<Option<T>> {
+    messages
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| i + 1 != index)
+        .map(|(_, message)| message.clone())
+        .collect()
+}
+
+fn run_protocol(nr_members: usize, misbehaving: bool) -> Transcript {
+    let mut rng = OsRng;
+    let threshold = (nr_members - 1) / 2;
+    let environment = Environment::init(
+        threshold,
+        nr_members,
+        b"benchmark string",
+        b"benchmark session",
+    );
+    // Members `1..=nr_misbehaving` do not broadcast in phase 3.
+    let nr_misbehaving = if misbehaving { threshold } else { 0 };
+
+    let keys: Vec<MemberCommunicationKey<Group>> =
+        MemberCommunicationKey::generate_committee(&mut rng, nr_members);
+    let pks: Vec<MemberCommunicationPublicKey<Group>> =
+        keys.iter().map(|key| key.to_public()).collect();
+
+    let mut members_1 = Vec::with_capacity(nr_members);
+    let mut broadcasts_1 = Vec::with_capacity(nr_members);
+    for (i, key) in keys.iter().enumerate() {
+        let (member, broadcast) =
+            DistributedKeyGeneration::init(&mut rng, &environment, key, &pks, i + 1);
+        members_1.push(member);
+        broadcasts_1.push(Some(broadcast));
+    }
+    let phase_1 = members_1[nr_members - 1].clone();
+
+    let mut members_2 = Vec::with_capacity(nr_members);
+    let mut broadcasts_2 = Vec::with_capacity(nr_members);
+    for (i, member) in members_1.into_iter().enumerate() {
+        let (member, broadcast) =
+            member.proceed_with_broadcast(&others(&broadcasts_1, i + 1), &mut rng);
+        members_2.push(member.expect("Phase 1 fails"));
+        broadcasts_2.push(broadcast);
+    }
+    let phase_2 = members_2[nr_members - 1].clone();
+
+    let mut members_3 = Vec::with_capacity(nr_members);
+    let mut broadcasts_3 = Vec::with_capacity(nr_members);
+    for (i, member) in members_2.into_iter().enumerate() {
+        let (member, broadcast) =
+            member.proceed_with_broadcast(&others(&broadcasts_2, i + 1), &broadcasts_1);
+        members_3.push(member.expect("Phase 2 fails"));
+        broadcasts_3.push(if i < nr_misbehaving { None } else { broadcast });
+    }
+    let phase_3 = members_3[nr_members - 1].clone();
+
+    let mut members_4 = Vec::with_capacity(nr_members);
+    let mut broadcasts_4 = vec![None; nr_members];
+    for (i, member) in members_3.into_iter().enumerate().skip(nr_misbehaving) {
+        let (member, broadcast) = member.proceed_with_broadcast(&others(&broadcasts_3, i + 1));
+        members_4.push((i, member.expect("Phase 3 fails")));
+        broadcasts_4[i] = broadcast;
+    }
+    let phase_4 = members_4[members_4.len() - 1].1.clone();
+
+    let broadcasts_1_refs: Vec<&BroadcastPhase1<Group>> = broadcasts_1
+        .iter()
+        .map(|broadcast| broadcast.as_ref().unwrap())
+        .collect();
+    let broadcasts_3_refs: Vec<&Option<BroadcastPhase3<Group>>> = broadcasts_3.iter().collect();
+    let mut members_5 = Vec::with_capacity(nr_members);
+    let mut broadcasts_5 = vec![None; nr_members];
+    for (i, member) in members_4 {
+        let (member, broadcast) = member.proceed_with_broadcast(
+            &others(&broadcasts_4, i + 1),
+            &broadcasts_1_refs,
+            &broadcasts_3_refs,
+        );
+        members_5.push(member.expect("Phase 4 fails"));
+        broadcasts_5[i] = broadcast;
+    }
+    let phase_5 = members_5[members_5.len() - 1].clone();
+
+    Transcript {
+        environment,
+        keys,
+        pks,
+        phase_1,
+        broadcasts_1,
+        phase_2,
+        broadcasts_2,
+        phase_3,
+        broadcasts_3,
+        phase_4,
+        broadcasts_4,
+        phase_5,
+        broadcasts_5,
+    }
+}
+
+/// Transcript which is only generated if one of the benchmarks that use it is run, so that
+/// filtering the benchmarks also skips the generation of the unused transcripts.
+struct LazyTranscript {
+    nr_members: usize,
+    misbehaving: bool,
+    transcript: RefCell<Option<Rc<Transcript>>>,
+}
+
+impl LazyTranscript {
+    fn new(nr_members: usize, misbehaving: bool) -> Self {
+        LazyTranscript {
+            nr_members,
+            misbehaving,
+            transcript: RefCell::new(None),
+        }
+    }
+
+    fn get(&self) -> Rc<Transcript> {
+        self.transcript
+            .borrow_mut()
+            .get_or_insert_with(|| Rc::new(run_protocol(self.nr_members, self.misbehaving)))
+            .clone()
+    }
+}
+
+fn phases(c: &mut Criterion) {
+    for &nr_members in COMMITTEE_SIZES.iter() {
+        for &misbehaving in [false, true].iter() {
+            let scenario = if misbehaving { "Misbehaving" } else { "Honest" };
+            let lazy_transcript = LazyTranscript::new(nr_members, misbehaving);
+            let index = nr_members;
+            let id = BenchmarkId::new(scenario, nr_members);
+
+            // The first phases do not depend on the misbehaviour of phase 3.
+            if !misbehaving {
+                let mut group = c.benchmark_group("Init");
+                group.sample_size(10);
+                group.bench_function(id.clone(), |b| {
+                    let transcript = lazy_transcript.get();
+                    b.iter(|| {
+                        DistributedKeyGeneration::init(
+                            &mut OsRng,
+                            &transcript.environment,
+                            &transcript.keys[index - 1],
+                            &transcript.pks,
+                            index,
+                        )
+                    })
+                });
+                group.finish();
+
+                let mut group = c.benchmark_group("Phase 1 proceed");
+                group.sample_size(10);
+                group.bench_function(id.clone(), |b| {
+                    let transcript = lazy_transcript.get();
+                    let broadcasts = others(&transcript.broadcasts_1, index);
+                    b.iter_batched(
+                        || transcript.phase_1.clone(),
+                        |phase| phase.proceed_with_broadcast(&broadcasts, &mut OsRng),
+                        BatchSize::SmallInput,
+                    )
+                });
+                group.finish();
+
+                let mut group = c.benchmark_group("Phase 2 proceed");
+                group.sample_size(10);
+                group.bench_function(id.clone(), |b| {
+                    let transcript = lazy_transcript.get();
+                    let broadcasts = others(&transcript.broadcasts_2, index);
+                    b.iter_batched(
+                        || transcript.phase_2.clone(),
+                        |phase| phase.proceed_with_broadcast(&broadcasts, &transcript.broadcasts_1),
+                        BatchSize::SmallInput,
+                    )
+                });
+                group.finish();
+            }
+
+            let mut group = c.benchmark_group("Phase 3 proceed");
+            group.sample_size(10);
+            group.bench_function(id.clone(), |b| {
+                let transcript = lazy_transcript.get();
+                let broadcasts = others(&transcript.broadcasts_3, index);
+                b.iter_batched(
+                    || transcript.phase_3.clone(),
+                    |phase| phase.proceed_with_broadcast(&broadcasts),
+                    BatchSize::SmallInput,
+                )
+            });
+            group.finish();
+
+            let mut group = c.benchmark_group("Phase 4 proceed");
+            group.sample_size(10);
+            group.bench_function(id.clone(), |b| {
+                let transcript = lazy_transcript.get();
+                let broadcasts = others(&transcript.broadcasts_4, index);
+                let broadcasts_1: Vec<&BroadcastPhase1<Group>> = transcript
+                    .broadcasts_1
+                    .iter()
+                    .map(|broadcast| broadcast.as_ref().unwrap())
+                    .collect();
+                let broadcasts_3: Vec<&Option<BroadcastPhase3<Group>>> =
+                    transcript.broadcasts_3.iter().collect();
+                b.iter_batched(
+                    || transcript.phase_4.clone(),
+                    |phase| phase.proceed_with_broadcast(&broadcasts, &broadcasts_1, &broadcasts_3),
+                    BatchSize::SmallInput,
+                )
+            });
+            group.finish();
+
+            let mut group = c.benchmark_group("Finalise");
+            group.sample_size(10);
+            group.bench_function(id, |b| {
+                let transcript = lazy_transcript.get();
+                let broadcasts = others(&transcript.broadcasts_5, index);
+                b.iter_batched(
+                    || transcript.phase_5.clone(),
+                    |phase| phase.finalise_with_broadcast(&broadcasts),
+                    BatchSize::SmallInput,
+                )
+            });
+            group.finish();
+        }
+    }
+}
+
+criterion_group!(benches, phases);
+criterion_main!(benches);
@@ -0,0 +1,86 @@
+//! Benchmarks of the building blocks of the protocol, with the sizes in which they are used
+//! by committees of different sizes, i.e. polynomials of degree `(nr_members - 1) / 2`.
+use blake2::Blake2b;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use rand_core::OsRng;
+use DKG::cryptography::dl_equality::DleqZkp;
+use DKG::dkg::procedure_keys::MemberCommunicationKey;
+use DKG::polynomial::{lagrange_interpolation, Polynomial};
+use DKG::traits::{PrimeGroupElement, Scalar as _};
+
+const COMMITTEE_SIZES: [usize; 6] = [4, 16, 64, 128, 256, 512];
+
+fn interpolation(c: &mut Criterion) {
+    let mut interpolate = c.benchmark_group("Polynomial::interpolate");
+    for &nr_members in COMMITTEE_SIZES.iter() {
+        let degree = (nr_members - 1) / 2;
+        let polynomial = Polynomial::<Scalar>::random(&mut OsRng, degree);
+        let indices: Vec<Scalar> = (1..=degree as u64 + 1).map(Scalar::from_u64).collect();
+        let evaluated_points: Vec<Scalar> = indices
+            .iter()
+            .map(|index| polynomial.evaluate(index))
+            .collect();
+
+        interpolate.bench_with_input(
+            BenchmarkId::from_parameter(nr_members),
+            &(evaluated_points, indices),
+            |b, (evaluated_points, indices)| {
+                b.iter(|| Polynomial::interpolate(degree, evaluated_points, indices))
+            },
+        );
+    }
+    interpolate.finish();
+
+    let mut lagrange = c.benchmark_group("lagrange_interpolation");
+    for &nr_members in COMMITTEE_SIZES.iter() {
+        let degree = (nr_members - 1) / 2;
+        let polynomial = Polynomial::<Scalar>::random(&mut OsRng, degree);
+        let indices: Vec<Scalar> = (1..=degree as u64 + 1).map(Scalar::from_u64).collect();
+        let evaluated_points: Vec<Scalar> = indices
+            .iter()
+            .map(|index| polynomial.evaluate(index))
+            .collect();
+
+        lagrange.bench_with_input(
+            BenchmarkId::from_parameter(nr_members),
+            &(evaluated_points, indices),
+            |b, (evaluated_points, indices)| {
+                b.iter(|| lagrange_interpolation(Scalar::zero(), evaluated_points, indices))
+            },
+        );
+    }
+    lagrange.finish();
+}
+
+fn dleq(c: &mut Criterion) {
+    let dlog = Scalar::random(&mut OsRng);
+    let base_1 = RistrettoPoint::generator();
+    let base_2 = RistrettoPoint::hash_to_group::<Blake2b>(b"benchmark base");
+    let point_1 = base_1 * dlog;
+    let point_2 = base_2 * dlog;
+
+    let mut group = c.benchmark_group("DleqZkp");
+    group.bench_function("generate", |b| {
+        b.iter(|| DleqZkp::generate(&base_1, &base_2, &point_1, &point_2, &dlog, &mut OsRng))
+    });
+    let proof = DleqZkp::generate(&base_1, &base_2, &point_1, &point_2, &dlog, &mut OsRng);
+    group.bench_function("verify", |b| {
+        b.iter(|| proof.verify(&base_1, &base_2, &point_1, &point_2))
+    });
+    group.finish();
+}
+
+fn hybrid_encryption(c: &mut Criterion) {
+    let pk = MemberCommunicationKey::<RistrettoPoint>::new(&mut OsRng).to_public();
+    // Shares are encrypted as the byte representation of a scalar.
+    let message = Scalar::random(&mut OsRng).to_bytes();
+
+    c.bench_function("hybrid_encrypt", |b| {
+        b.iter(|| pk.hybrid_encrypt(&message, &mut OsRng))
+    });
+}
+
+criterion_group!(benches, interpolation, dleq, hybrid_encryption);
+criterion_main!(benches);
@@ -0,0 +1,106 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use rand_core::OsRng;
+use DKG::dkg::broadcast::{BroadcastPhase1, DecryptedShares};
+use DKG::dkg::committee::{DistributedKeyGeneration, Environment};
+use DKG::dkg::procedure_keys::{MemberCommunicationKey, MemberCommunicationPublicKey};
+use DKG::traits::{PrimeGroupElement, Scalar};
+
+type Group = RistrettoPoint;
+type GroupScalar = <Group as PrimeGroupElement>::CorrespondingScalar;
+
+const COMMITTEE_SIZES: [usize; 3] = [16, 64, 128];
+
+struct Setup {
+    environment: Environment<Group>,
+    keys: Vec<MemberCommunicationKey<Group>>,
+    broadcasts: Vec<Option<BroadcastPhase1<Group>>>,
+}
+
+fn setup(nr_members: usize) -> Setup {
+    let mut rng = OsRng;
+    let environment = Environment::init(
+        (nr_members - 1) / 2,
+        nr_members,
+        b"benchmark string",
+        b"benchmark session",
+    );
+    let keys: Vec<MemberCommunicationKey<Group>> =
+        MemberCommunicationKey::generate_committee(&mut rng, nr_members);
+    let pks: Vec<MemberCommunicationPublicKey<Group>> =
+        keys.iter().map(|key| key.to_public()).collect();
+    let broadcasts = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| {
+            Some(DistributedKeyGeneration::init(&mut rng, &environment, key, &pks, i + 1).1)
+        })
+        .collect();
+
+    Setup {
+        environment,
+        keys,
+        broadcasts,
+    }
+}
+
+// Shares received by the last member from every other member. We use the last member, as the
+// checks of member 1 are cheaper than average, with all powers of its index being one.
+fn received_shares(setup: &Setup) -> Vec<DecryptedShares<Group>> {
+    let recipient = setup.keys.len();
+    setup.broadcasts[..recipient - 1]
+        .iter()
+        .map(|broadcast| {
+            let broadcast = broadcast.as_ref().unwrap();
+            let shares = &broadcast.encrypted_shares[recipient - 1];
+            let decrypt = |ciphertext| {
+                GroupScalar::from_bytes(&setup.keys[recipient - 1].hybrid_decrypt(ciphertext))
+                    .unwrap()
+            };
+            DecryptedShares {
+                decrypted_share: decrypt(&shares.encrypted_share),
+                decrypted_randomness: decrypt(&shares.encrypted_randomness),
+                committed_coefficients: broadcast.committed_coefficients.clone(),
+            }
+        })
+        .collect()
+}
+
+fn share_checks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Share checks");
+    for &nr_members in COMMITTEE_SIZES.iter() {
+        let setup = setup(nr_members);
+        let shares = received_shares(&setup);
+        let share_refs: Vec<&DecryptedShares<Group>> = shares.iter().collect();
+
+        group.bench_with_input(
+            BenchmarkId::new("Per dealer", nr_members),
+            &shares,
+            |b, shares| {
+                b.iter(|| {
+                    shares
+                        .iter()
+                        .all(|share| share.verify(&setup.environment, nr_members))
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Batched", nr_members),
+            &share_refs,
+            |b, share_refs| {
+                b.iter(|| {
+                    DecryptedShares::batch_verify(
+                        &setup.environment,
+                        nr_members,
+                        share_refs,
+                        &mut OsRng,
+                    )
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, share_checks);
+criterion_main!(benches);
@@ -0,0 +1,300 @@
+//! Vectors of lifted ElGamal ciphertexts, with element-wise homomorphic operations. A vote
+//! for one of `n` options is encrypted as a unit vector of size `n`, and the sum of the
+//! encrypted votes is the encrypted tally of every option.
+use crate::cryptography::commitment::CommitmentKey;
+use crate::cryptography::discrete_log::BabyStepGiantStep;
+use crate::cryptography::elgamal::{Ciphertext, PublicKey, SecretKey};
+use crate::cryptography::unit_vector::UnitVectorZkp;
+use crate::errors::ProofError;
+use crate::traits::{PrimeGroupElement, Scalar};
+use rand_core::{CryptoRng, RngCore};
+use std::ops::{Add, Mul, Sub};
+
+/// Vector of ElGamal ciphertexts, encrypted under the same public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiphertextVector<G: PrimeGroupElement> {
+    ciphertexts: Vec<Ciphertext<G>>,
+}
+
+impl<G: PrimeGroupElement> CiphertextVector<G> {
+    /// Vector of `size` zero ciphertexts, the neutral element of the addition.
+    pub fn zero(size: usize) -> Self {
+        CiphertextVector {
+            ciphertexts: vec![Ciphertext::zero(); size],
+        }
+    }
+
+    pub fn from_ciphertexts(ciphertexts: Vec<Ciphertext<G>>) -> Self {
+        CiphertextVector { ciphertexts }
+    }
+
+    /// Encrypt every message of `messages` under `public_key` with lifted ElGamal.
+    pub fn encrypt<R>(
+        public_key: &PublicKey<G>,
+        messages: &[G::CorrespondingScalar],
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        CiphertextVector {
+            ciphertexts: messages
+                .iter()
+                .map(|message| public_key.encrypt(message, rng))
+                .collect(),
+        }
+    }
+
+    /// Encrypt the unit vector of size `size` with a one at position `choice`, and zeros
+    /// elsewhere.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `choice` is not smaller than `size`.
+    pub fn encrypt_unit_vector<R>(
+        public_key: &PublicKey<G>,
+        choice: usize,
+        size: usize,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        Self::encrypt(public_key, &unit_vector::<G>(choice, size), rng)
+    }
+
+    /// Encrypt the unit vector of size `size` with a one at position `choice`, together
+    /// with a proof that the resulting vector encrypts a unit vector, which does not reveal
+    /// `choice`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `choice` is not smaller than `size`.
+    pub fn encrypt_unit_vector_with_proof<R>(
+        public_key: &PublicKey<G>,
+        commitment_key: &CommitmentKey<G>,
+        choice: usize,
+        size: usize,
+        rng: &mut R,
+    ) -> (Self, UnitVectorZkp<G>)
+    where
+        R: CryptoRng + RngCore,
+    {
+        let (ciphertexts, randomness): (Vec<Ciphertext<G>>, Vec<G::CorrespondingScalar>) =
+            unit_vector::<G>(choice, size)
+                .iter()
+                .map(|message| public_key.encrypt_return_r(message, rng))
+                .unzip();
+        let proof = UnitVectorZkp::generate(
+            public_key,
+            commitment_key,
+            &ciphertexts,
+            &randomness,
+            choice,
+            rng,
+        );
+        (CiphertextVector { ciphertexts }, proof)
+    }
+
+    /// Verify that the vector encrypts a unit vector under `public_key`.
+    pub fn verify_unit_vector(
+        &self,
+        public_key: &PublicKey<G>,
+        commitment_key: &CommitmentKey<G>,
+        proof: &UnitVectorZkp<G>,
+    ) -> Result<(), ProofError> {
+        proof.verify(public_key, commitment_key, &self.ciphertexts)
+    }
+
+    /// Decrypt every ciphertext with `secret_key`, and solve the discrete logarithm of the
+    /// plaintexts with `table`. Returns `None` if any of the plaintexts is larger than the
+    /// bound of the table.
+    pub fn decrypt_bounded(
+        &self,
+        secret_key: &SecretKey<G>,
+        table: &BabyStepGiantStep<G>,
+    ) -> Option<Vec<u64>> {
+        self.ciphertexts
+            .iter()
+            .map(|ciphertext| secret_key.decrypt_scalar_bounded(ciphertext, table))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ciphertexts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ciphertexts.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[Ciphertext<G>] {
+        &self.ciphertexts
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Ciphertext<G>> {
+        self.ciphertexts.iter()
+    }
+}
+
+// Plaintexts of the unit vector of size `size` with a one at position `choice`.
+fn unit_vector<G: PrimeGroupElement>(choice: usize, size: usize) -> Vec<G::CorrespondingScalar> {
+    assert!(choice < size);
+    (0..size)
+        .map(|i| {
+            if i == choice {
+                G::CorrespondingScalar::one()
+            } else {
+                G::CorrespondingScalar::zero()
+            }
+        })
+        .collect()
+}
+
+/// Element-wise addition of the ciphertexts. Both vectors must have the same length.
+impl<G: PrimeGroupElement> Add<&CiphertextVector<G>> for &CiphertextVector<G> {
+    type Output = CiphertextVector<G>;
+
+    fn add(self, other: &CiphertextVector<G>) -> CiphertextVector<G> {
+        assert_eq!(self.len(), other.len());
+        CiphertextVector {
+            ciphertexts: self
+                .ciphertexts
+                .iter()
+                .zip(other.ciphertexts.iter())
+                .map(|(a, b)| a + b)
+                .collect(),
+        }
+    }
+}
+
+std_ops_gen!(
+    CiphertextVector,
+    PrimeGroupElement,
+    Add,
+    CiphertextVector,
+    CiphertextVector,
+    add
+);
+
+/// Element-wise subtraction of the ciphertexts. Both vectors must have the same length.
+impl<G: PrimeGroupElement> Sub<&CiphertextVector<G>> for &CiphertextVector<G> {
+    type Output = CiphertextVector<G>;
+
+    fn sub(self, other: &CiphertextVector<G>) -> CiphertextVector<G> {
+        assert_eq!(self.len(), other.len());
+        CiphertextVector {
+            ciphertexts: self
+                .ciphertexts
+                .iter()
+                .zip(other.ciphertexts.iter())
+                .map(|(a, b)| a - b)
+                .collect(),
+        }
+    }
+}
+
+std_ops_gen!(
+    CiphertextVector,
+    PrimeGroupElement,
+    Sub,
+    CiphertextVector,
+    CiphertextVector,
+    sub
+);
+
+impl<G: PrimeGroupElement> Mul<&G::CorrespondingScalar> for &CiphertextVector<G> {
+    type Output = CiphertextVector<G>;
+
+    fn mul(self, rhs: &G::CorrespondingScalar) -> Self::Output {
+        CiphertextVector {
+            ciphertexts: self
+                .ciphertexts
+                .iter()
+                .map(|ciphertext| ciphertext * rhs)
+                .collect(),
+        }
+    }
+}
+
+std_ops_gen_nsym!(
+    CiphertextVector,
+    PrimeGroupElement,
+    Mul,
+    CiphertextVector,
+    mul
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptography::elgamal::Keypair;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar as RScalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn encrypted_tally() {
+        let mut rng = OsRng;
+        let keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        let table = BabyStepGiantStep::new(100);
+
+        let votes = [0usize, 2, 2, 1, 2];
+        let tally = votes
+            .iter()
+            .fold(CiphertextVector::zero(3), |tally, &vote| {
+                tally
+                    + CiphertextVector::encrypt_unit_vector(&keypair.public_key, vote, 3, &mut rng)
+            });
+        assert_eq!(tally.len(), 3);
+        assert_eq!(
+            tally.decrypt_bounded(&keypair.secret_key, &table),
+            Some(vec![1, 1, 3])
+        );
+
+        let messages = [RScalar::from(4u64), RScalar::from(7u64), RScalar::zero()];
+        let encrypted = CiphertextVector::encrypt(&keypair.public_key, &messages, &mut rng);
+        assert_eq!(
+            (&(&encrypted + &tally) - &tally).decrypt_bounded(&keypair.secret_key, &table),
+            Some(vec![4, 7, 0])
+        );
+        // The last plaintext, 0 - 3, is out of the bounds of the table.
+        assert_eq!(
+            (&encrypted - &tally).decrypt_bounded(&keypair.secret_key, &table),
+            None
+        );
+        assert_eq!(
+            (encrypted * &RScalar::from(3u64)).decrypt_bounded(&keypair.secret_key, &table),
+            Some(vec![12, 21, 0])
+        );
+    }
+
+    #[test]
+    fn unit_vector_ballot() {
+        let mut rng = OsRng;
+        let keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        let commitment_key = CommitmentKey::<RistrettoPoint>::generate(&[0u8]);
+        let table = BabyStepGiantStep::new(1);
+
+        let (ballot, proof) = CiphertextVector::encrypt_unit_vector_with_proof(
+            &keypair.public_key,
+            &commitment_key,
+            2,
+            3,
+            &mut rng,
+        );
+        assert!(ballot
+            .verify_unit_vector(&keypair.public_key, &commitment_key, &proof)
+            .is_ok());
+        assert_eq!(
+            ballot.decrypt_bounded(&keypair.secret_key, &table),
+            Some(vec![0, 0, 1])
+        );
+
+        // A ballot voting twice for the same option does not verify.
+        let doubled = &ballot * &RScalar::from(2u64);
+        assert!(doubled
+            .verify_unit_vector(&keypair.public_key, &commitment_key, &proof)
+            .is_err());
+    }
+}
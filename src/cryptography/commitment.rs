@@ -1,28 +1,84 @@
-use crate::traits::{PrimeGroupElement, Scalar};
+use crate::traits::{FixedBaseTable, PrimeGroupElement, Scalar};
 use blake2::Blake2b;
 use rand_core::{CryptoRng, RngCore};
+use std::ops::{Add, Mul, Sub};
+use std::sync::Arc;
 
-/// Pedersen Commitment key
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Pedersen Commitment key. If the group supports it, the key caches the precomputed table of
+/// `h`, so that commitments use fixed base multiplications. The table is shared among the
+/// clones of the key, which are cheap, but the key is no longer `Copy`. For the same reason,
+/// `h` is read with `CommitmentKey::h`, and a key with a given `h` is built with
+/// `CommitmentKey::from_h`.
 pub struct CommitmentKey<G: PrimeGroupElement> {
-    pub h: G,
+    h: G,
+    h_table: Option<Arc<dyn FixedBaseTable<G>>>,
 }
 
+impl<G: PrimeGroupElement> Clone for CommitmentKey<G> {
+    fn clone(&self) -> Self {
+        CommitmentKey {
+            h: self.h,
+            h_table: self.h_table.clone(),
+        }
+    }
+}
+
+impl<G: PrimeGroupElement> std::fmt::Debug for CommitmentKey<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommitmentKey")
+            .field("h", &self.h)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: PrimeGroupElement> PartialEq for CommitmentKey<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.h == other.h
+    }
+}
+
+impl<G: PrimeGroupElement> Eq for CommitmentKey<G> {}
+
 impl<G: PrimeGroupElement> CommitmentKey<G> {
     /// Generate a new random commitment key by hashin the input
     pub fn generate(bytes: &[u8]) -> Self {
+        Self::from_h(G::hash_to_group::<Blake2b>(bytes))
+    }
+
+    /// Commitment key with the given `h`. The discrete logarithm of `h` with respect to the
+    /// generator must be unknown for the commitments to be binding.
+    pub fn from_h(h: G) -> Self {
         CommitmentKey::<G> {
-            h: G::hash_to_group::<Blake2b>(bytes),
+            h,
+            h_table: h.fixed_base_table(),
         }
     }
+
+    /// Second base of the commitment key, `h`.
+    pub fn h(&self) -> G {
+        self.h
+    }
+
+    /// Returns `h * r`, using the precomputed table of `h` if the group supports it.
+    pub fn h_mul(&self, r: &G::CorrespondingScalar) -> G {
+        match &self.h_table {
+            Some(table) => table.mul(r),
+            None => self.h * *r,
+        }
+    }
+
     /// Return a commitment with the given opening, `o`
     pub fn commit_with_open(&self, o: &Open<G>) -> G {
         self.commit_with_random(&o.m, &o.r)
     }
 
     // Return a commitment with the given message, `m`,  and opening key, `r`
-    fn commit_with_random(&self, m: &G::CorrespondingScalar, r: &G::CorrespondingScalar) -> G {
-        G::generator() * *m + self.h * *r
+    pub(crate) fn commit_with_random(
+        &self,
+        m: &G::CorrespondingScalar,
+        r: &G::CorrespondingScalar,
+    ) -> G {
+        G::generator_mul(m) + self.h_mul(r)
     }
 
     /// Return a commitment, and the used randomness, `r`, where the latter is computed
@@ -43,9 +99,9 @@ impl<G: PrimeGroupElement> CommitmentKey<G> {
     {
         let r = G::CorrespondingScalar::random(rng);
         if m {
-            (G::generator() + self.h * r, r)
+            (G::generator() + self.h_mul(&r), r)
         } else {
-            (self.h * r, r)
+            (self.h_mul(&r), r)
         }
     }
 
@@ -57,12 +113,206 @@ impl<G: PrimeGroupElement> CommitmentKey<G> {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 pub struct Open<G: PrimeGroupElement> {
     pub m: G::CorrespondingScalar,
     pub r: G::CorrespondingScalar,
 }
 
+impl<G: PrimeGroupElement> std::fmt::Debug for Open<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Open").finish_non_exhaustive()
+    }
+}
+
+/// Pedersen vector commitment key, with generators `g_1, ..., g_n` for the messages and `h`
+/// for the randomness. A vector of messages `m` is committed as
+/// \\( h^r \prod_i g_i^{m_i} \\).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VectorCommitmentKey<G: PrimeGroupElement> {
+    generators: Vec<G>,
+    blinding_key: CommitmentKey<G>,
+}
+
+impl<G: PrimeGroupElement> VectorCommitmentKey<G> {
+    const DOMAIN: &'static [u8] = b"DKG vector commitment key";
+
+    /// Derive a commitment key for vectors of up to `size` messages from `seed`. Every
+    /// generator is hashed to the group from the seed with its own index, `0` for `h` and `i`
+    /// for `g_i`, so that anyone can verify that their discrete logarithms are unknown. The
+    /// generators of a smaller key are a prefix of the ones of a larger key with the same
+    /// seed.
+    pub fn generate(seed: &[u8], size: usize) -> Self {
+        let mut bytes = Self::DOMAIN.to_vec();
+        bytes.extend_from_slice(&(seed.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(seed);
+        let generator = |index: usize| {
+            let mut bytes = bytes.clone();
+            bytes.extend_from_slice(&(index as u64).to_le_bytes());
+            G::hash_to_group::<Blake2b>(&bytes)
+        };
+
+        VectorCommitmentKey {
+            generators: (1..=size).map(generator).collect(),
+            blinding_key: CommitmentKey::from_h(generator(0)),
+        }
+    }
+
+    /// Maximum number of messages of a commitment.
+    pub fn len(&self) -> usize {
+        self.generators.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.generators.is_empty()
+    }
+
+    /// Generators of the messages, `g_1, ..., g_n`.
+    pub fn generators(&self) -> &[G] {
+        &self.generators
+    }
+
+    /// Generator of the randomness, `h`.
+    pub fn h(&self) -> G {
+        self.blinding_key.h()
+    }
+
+    /// Returns `h * r`, using the precomputed table of `h`.
+    pub fn h_mul(&self, r: &G::CorrespondingScalar) -> G {
+        self.blinding_key.h_mul(r)
+    }
+
+    /// Return a commitment with the given opening, `o`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the opening has more messages than the length of the key.
+    pub fn commit_with_open(&self, o: &VectorOpen<G>) -> G {
+        self.commit_vec_with_random(&o.m, &o.r)
+    }
+
+    // Return a commitment with the given messages, `m`, and opening key, `r`. The messages are
+    // secret, so the commitment does not use variable time multiscalar multiplication.
+    pub(crate) fn commit_vec_with_random(
+        &self,
+        m: &[G::CorrespondingScalar],
+        r: &G::CorrespondingScalar,
+    ) -> G {
+        assert!(m.len() <= self.len());
+        self.generators
+            .iter()
+            .zip(m.iter())
+            .fold(self.h_mul(r), |commitment, (g, m)| commitment + *g * *m)
+    }
+
+    /// Return a commitment to the vector of messages, `m`, and the used randomness, `r`,
+    /// where the latter is computed from a `Rng + CryptoRng`
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more messages than the length of the key.
+    pub fn commit_vec<R>(
+        &self,
+        m: &[G::CorrespondingScalar],
+        rng: &mut R,
+    ) -> (G, G::CorrespondingScalar)
+    where
+        R: CryptoRng + RngCore,
+    {
+        let r = G::CorrespondingScalar::random(rng);
+        (self.commit_vec_with_random(m, &r), r)
+    }
+
+    /// Verify that a given `commitment` opens to `o` under commitment key `self`. Returns
+    /// false if the opening has more messages than the length of the key.
+    pub fn verify(&self, commitment: &G, o: &VectorOpen<G>) -> bool {
+        o.m.len() <= self.len() && commitment == &self.commit_with_open(o)
+    }
+}
+
+/// Opening of a vector commitment. Commitments are homomorphic: the sum of two commitments
+/// opens to the sum of their openings, and a multiple of a commitment to the multiple of its
+/// opening.
+#[derive(Clone, Eq, PartialEq)]
+pub struct VectorOpen<G: PrimeGroupElement> {
+    pub m: Vec<G::CorrespondingScalar>,
+    pub r: G::CorrespondingScalar,
+}
+
+impl<G: PrimeGroupElement> std::fmt::Debug for VectorOpen<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VectorOpen").finish_non_exhaustive()
+    }
+}
+
+/// Element-wise addition of the openings. Both openings must have the same number of messages.
+impl<G: PrimeGroupElement> Add<&VectorOpen<G>> for &VectorOpen<G> {
+    type Output = VectorOpen<G>;
+
+    fn add(self, other: &VectorOpen<G>) -> VectorOpen<G> {
+        assert_eq!(self.m.len(), other.m.len());
+        VectorOpen {
+            m: self
+                .m
+                .iter()
+                .zip(other.m.iter())
+                .map(|(a, b)| *a + *b)
+                .collect(),
+            r: self.r + other.r,
+        }
+    }
+}
+
+std_ops_gen!(
+    VectorOpen,
+    PrimeGroupElement,
+    Add,
+    VectorOpen,
+    VectorOpen,
+    add
+);
+
+/// Element-wise subtraction of the openings. Both openings must have the same number of
+/// messages.
+impl<G: PrimeGroupElement> Sub<&VectorOpen<G>> for &VectorOpen<G> {
+    type Output = VectorOpen<G>;
+
+    fn sub(self, other: &VectorOpen<G>) -> VectorOpen<G> {
+        assert_eq!(self.m.len(), other.m.len());
+        VectorOpen {
+            m: self
+                .m
+                .iter()
+                .zip(other.m.iter())
+                .map(|(a, b)| *a - *b)
+                .collect(),
+            r: self.r - other.r,
+        }
+    }
+}
+
+std_ops_gen!(
+    VectorOpen,
+    PrimeGroupElement,
+    Sub,
+    VectorOpen,
+    VectorOpen,
+    sub
+);
+
+impl<G: PrimeGroupElement> Mul<&G::CorrespondingScalar> for &VectorOpen<G> {
+    type Output = VectorOpen<G>;
+
+    fn mul(self, rhs: &G::CorrespondingScalar) -> Self::Output {
+        VectorOpen {
+            m: self.m.iter().map(|m| *m * *rhs).collect(),
+            r: self.r * *rhs,
+        }
+    }
+}
+
+std_ops_gen_nsym!(VectorOpen, PrimeGroupElement, Mul, VectorOpen, mul);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,6 +322,24 @@ mod tests {
 
     use rand_core::OsRng;
 
+    #[test]
+    fn fixed_base_multiplication() {
+        let mut rng = OsRng;
+        let commitment_key = CommitmentKey::<RistrettoPoint>::generate(&[0u8]);
+        let scalar = RScalar::random(&mut rng);
+
+        assert!(commitment_key.h_table.is_some());
+        assert_eq!(commitment_key.h_mul(&scalar), commitment_key.h() * scalar);
+        assert_eq!(
+            RistrettoPoint::generator_mul(&scalar),
+            RistrettoPoint::generator() * scalar
+        );
+        assert_eq!(
+            commitment_key.clone(),
+            CommitmentKey::from_h(commitment_key.h())
+        );
+    }
+
     #[test]
     fn commit_and_open() {
         let mut rng = OsRng;
@@ -94,4 +362,60 @@ mod tests {
 
         assert_eq!(comm_with_open, comm);
     }
+
+    #[test]
+    fn vector_commitment_key() {
+        let commitment_key = VectorCommitmentKey::<RistrettoPoint>::generate(&[0u8], 3);
+        assert_eq!(commitment_key.len(), 3);
+        // The generators are derived deterministically, and a smaller key is a prefix.
+        let smaller_key = VectorCommitmentKey::<RistrettoPoint>::generate(&[0u8], 2);
+        assert_eq!(smaller_key.h(), commitment_key.h());
+        assert_eq!(smaller_key.generators(), &commitment_key.generators()[..2]);
+        assert_ne!(
+            VectorCommitmentKey::<RistrettoPoint>::generate(&[1u8], 3),
+            commitment_key
+        );
+    }
+
+    #[test]
+    fn vector_commit_and_open() {
+        let mut rng = OsRng;
+        let commitment_key = VectorCommitmentKey::<RistrettoPoint>::generate(&[0u8], 3);
+        let smaller_key = VectorCommitmentKey::<RistrettoPoint>::generate(&[0u8], 2);
+        let messages: Vec<RScalar> = (0..3).map(|_| RScalar::random(&mut rng)).collect();
+        let (comm, rand) = commitment_key.commit_vec(&messages, &mut rng);
+        let opening = VectorOpen {
+            m: messages.clone(),
+            r: rand,
+        };
+        assert!(commitment_key.verify(&comm, &opening));
+        assert_eq!(commitment_key.commit_with_open(&opening), comm);
+
+        let mut wrong_opening = opening.clone();
+        wrong_opening.m[2] += RScalar::one();
+        assert!(!commitment_key.verify(&comm, &wrong_opening));
+        assert!(!smaller_key.verify(&comm, &opening));
+
+        // Fewer messages are committed with the first generators.
+        let (short_comm, short_rand) = commitment_key.commit_vec(&messages[..2], &mut rng);
+        assert_eq!(
+            smaller_key.commit_with_open(&VectorOpen {
+                m: messages[..2].to_vec(),
+                r: short_rand,
+            }),
+            short_comm
+        );
+
+        // Homomorphic operations on the commitments and the openings.
+        let other_messages: Vec<RScalar> = (0..3).map(|_| RScalar::random(&mut rng)).collect();
+        let (other_comm, other_rand) = commitment_key.commit_vec(&other_messages, &mut rng);
+        let other_opening = VectorOpen {
+            m: other_messages,
+            r: other_rand,
+        };
+        let scalar = RScalar::random(&mut rng);
+        assert!(commitment_key.verify(&(comm + other_comm), &(&opening + &other_opening)));
+        assert!(commitment_key.verify(&(comm - other_comm), &(&opening - &other_opening)));
+        assert!(commitment_key.verify(&(comm * scalar), &(opening * &scalar)));
+    }
 }
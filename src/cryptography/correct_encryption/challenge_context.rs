@@ -0,0 +1,45 @@
+use crate::cryptography::elgamal::{Ciphertext, PublicKey};
+use crate::traits::{PrimeGroupElement, Scalar};
+use blake2::Blake2b;
+
+/// Challenge context for the proofs of correct encryption. The common reference string is the
+/// public key of the encryption, and the statement consists of the ciphertext together with
+/// the group elements defining its plaintext, i.e. the encoding of the message or the
+/// commitment key and the commitment. The label separates the domain of each proof.
+#[derive(Debug, Clone)]
+pub struct ChallengeContext(Vec<u8>);
+
+impl ChallengeContext {
+    /// Initialise the challenge context, by including the label of the proof, the common
+    /// reference string, the ciphertext and the remaining elements of the statement.
+    pub(crate) fn new<G: PrimeGroupElement>(
+        label: &[u8],
+        public_key: &PublicKey<G>,
+        ciphertext: &Ciphertext<G>,
+        statement: &[G],
+    ) -> Self {
+        let mut ctx: Vec<u8> = label.to_vec();
+        ctx.extend_from_slice(&public_key.pk.to_bytes());
+        ctx.extend_from_slice(&ciphertext.e1.to_bytes());
+        ctx.extend_from_slice(&ciphertext.e2.to_bytes());
+        for point in statement {
+            ctx.extend_from_slice(&point.to_bytes());
+        }
+
+        ChallengeContext(ctx)
+    }
+
+    /// Generation of the `first_challenge`. This challenge is generated after the
+    /// `announcements` are "sent". Hence, we include the latter to the challenge context and
+    /// generate its corresponding scalar.
+    pub(crate) fn first_challenge<G: PrimeGroupElement>(
+        &mut self,
+        announcements: &[G],
+    ) -> G::CorrespondingScalar {
+        for announcement in announcements {
+            self.0.extend_from_slice(&announcement.to_bytes());
+        }
+
+        <G as PrimeGroupElement>::CorrespondingScalar::hash_to_scalar::<Blake2b>(&self.0)
+    }
+}
@@ -0,0 +1,288 @@
+//! Non-interactive Zero Knowledge proofs of correct encryption of lifted ElGamal ciphertexts,
+//! \\( (e_1, e_2) = (g^r, g^m pk^r) \\), which do not reveal the randomness `r`.
+//!
+//! The first proof shows that the ciphertext encrypts a public message `m`:
+//!
+//! `NIZK{(pk, (e1, e2), m), (r): e1 = g^r AND e2 = g^m pk^r}`
+//!
+//! The second proof shows that the ciphertext encrypts the value of a Pedersen commitment
+//! `C = g^m h^s`, without revealing it:
+//!
+//! `NIZK{(pk, h, (e1, e2), C), (m, r, s): e1 = g^r AND e2 = g^m pk^r AND C = g^m h^s}`
+use super::challenge_context::ChallengeContext;
+use crate::cryptography::commitment::{CommitmentKey, Open};
+use crate::cryptography::elgamal::{Ciphertext, PublicKey};
+use crate::errors::ProofError;
+use crate::traits::{PrimeGroupElement, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+/// Proof that a ciphertext encrypts a known message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownPlaintextZkp<G: PrimeGroupElement> {
+    challenge: G::CorrespondingScalar,
+    response: G::CorrespondingScalar,
+}
+
+impl<G: PrimeGroupElement> KnownPlaintextZkp<G> {
+    const LABEL: &'static [u8] = b"known plaintext";
+
+    /// Generate a proof that `ciphertext` is the encryption of `message` under `public_key`,
+    /// with `randomness`, e.g. as returned by `PublicKey::encrypt_return_r`.
+    pub fn generate<R>(
+        public_key: &PublicKey<G>,
+        ciphertext: &Ciphertext<G>,
+        message: &G::CorrespondingScalar,
+        randomness: &G::CorrespondingScalar,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let w = G::CorrespondingScalar::random(rng);
+        let announcement_1 = G::generator_mul(&w);
+        let announcement_2 = public_key.pk * w;
+        let mut challenge_context = ChallengeContext::new(
+            Self::LABEL,
+            public_key,
+            ciphertext,
+            &[G::generator_mul(message)],
+        );
+        let challenge = challenge_context.first_challenge(&[announcement_1, announcement_2]);
+        let response = challenge * *randomness + w;
+
+        KnownPlaintextZkp {
+            challenge,
+            response,
+        }
+    }
+
+    /// Verify that `ciphertext` encrypts `message` under `public_key`.
+    pub fn verify(
+        &self,
+        public_key: &PublicKey<G>,
+        ciphertext: &Ciphertext<G>,
+        message: &G::CorrespondingScalar,
+    ) -> Result<(), ProofError> {
+        let message_point = G::generator_mul(message);
+        // g^z = a1 * e1^c and pk^z = a2 * (e2 / g^m)^c
+        let announcement_1 = G::generator_mul(&self.response) - ciphertext.e1 * self.challenge;
+        let announcement_2 =
+            public_key.pk * self.response - (ciphertext.e2 - message_point) * self.challenge;
+
+        let mut challenge_context =
+            ChallengeContext::new(Self::LABEL, public_key, ciphertext, &[message_point]);
+        let challenge = challenge_context.first_challenge(&[announcement_1, announcement_2]);
+        // no need for constant time equality because of the hash in challenge()
+        if challenge == self.challenge {
+            Ok(())
+        } else {
+            Err(ProofError::ZkpVerificationFailed)
+        }
+    }
+}
+
+/// Proof that a ciphertext encrypts the value of a Pedersen commitment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommittedPlaintextZkp<G: PrimeGroupElement> {
+    challenge: G::CorrespondingScalar,
+    response_message: G::CorrespondingScalar,
+    response_encryption: G::CorrespondingScalar,
+    response_commitment: G::CorrespondingScalar,
+}
+
+impl<G: PrimeGroupElement> CommittedPlaintextZkp<G> {
+    const LABEL: &'static [u8] = b"committed plaintext";
+
+    /// Generate a proof that `ciphertext`, encrypted under `public_key` with
+    /// `encryption_randomness`, encrypts the value of `commitment`, where `opening` is the
+    /// opening of the latter under `commitment_key`.
+    pub fn generate<R>(
+        public_key: &PublicKey<G>,
+        commitment_key: &CommitmentKey<G>,
+        ciphertext: &Ciphertext<G>,
+        commitment: &G,
+        opening: &Open<G>,
+        encryption_randomness: &G::CorrespondingScalar,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let w_message = G::CorrespondingScalar::random(rng);
+        let w_encryption = G::CorrespondingScalar::random(rng);
+        let w_commitment = G::CorrespondingScalar::random(rng);
+        let announcements = [
+            G::generator_mul(&w_encryption),
+            G::generator_mul(&w_message) + public_key.pk * w_encryption,
+            commitment_key.commit_with_random(&w_message, &w_commitment),
+        ];
+        let mut challenge_context = ChallengeContext::new(
+            Self::LABEL,
+            public_key,
+            ciphertext,
+            &[commitment_key.h(), *commitment],
+        );
+        let challenge = challenge_context.first_challenge(&announcements);
+
+        CommittedPlaintextZkp {
+            challenge,
+            response_message: challenge * opening.m + w_message,
+            response_encryption: challenge * *encryption_randomness + w_encryption,
+            response_commitment: challenge * opening.r + w_commitment,
+        }
+    }
+
+    /// Verify that `ciphertext` encrypts, under `public_key`, the value committed in
+    /// `commitment` under `commitment_key`.
+    pub fn verify(
+        &self,
+        public_key: &PublicKey<G>,
+        commitment_key: &CommitmentKey<G>,
+        ciphertext: &Ciphertext<G>,
+        commitment: &G,
+    ) -> Result<(), ProofError> {
+        let announcements = [
+            G::generator_mul(&self.response_encryption) - ciphertext.e1 * self.challenge,
+            G::generator_mul(&self.response_message) + public_key.pk * self.response_encryption
+                - ciphertext.e2 * self.challenge,
+            commitment_key.commit_with_random(&self.response_message, &self.response_commitment)
+                - *commitment * self.challenge,
+        ];
+
+        let mut challenge_context = ChallengeContext::new(
+            Self::LABEL,
+            public_key,
+            ciphertext,
+            &[commitment_key.h(), *commitment],
+        );
+        let challenge = challenge_context.first_challenge(&announcements);
+        // no need for constant time equality because of the hash in challenge()
+        if challenge == self.challenge {
+            Ok(())
+        } else {
+            Err(ProofError::ZkpVerificationFailed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptography::elgamal::Keypair;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar as RScalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn known_plaintext() {
+        let mut rng = OsRng;
+        let keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        let message = RScalar::random(&mut rng);
+        let (ciphertext, randomness) = keypair.public_key.encrypt_return_r(&message, &mut rng);
+
+        let proof = KnownPlaintextZkp::generate(
+            &keypair.public_key,
+            &ciphertext,
+            &message,
+            &randomness,
+            &mut rng,
+        );
+        assert!(proof
+            .verify(&keypair.public_key, &ciphertext, &message)
+            .is_ok());
+
+        let other_message = message + RScalar::one();
+        assert!(proof
+            .verify(&keypair.public_key, &ciphertext, &other_message)
+            .is_err());
+        let other_ciphertext = keypair
+            .public_key
+            .encrypt_with_r(&message, &(randomness + RScalar::one()));
+        assert!(proof
+            .verify(&keypair.public_key, &other_ciphertext, &message)
+            .is_err());
+
+        // A ciphertext of a different message cannot be proven to encrypt `message`.
+        let (ciphertext, randomness) = keypair
+            .public_key
+            .encrypt_return_r(&other_message, &mut rng);
+        let proof = KnownPlaintextZkp::generate(
+            &keypair.public_key,
+            &ciphertext,
+            &message,
+            &randomness,
+            &mut rng,
+        );
+        assert!(proof
+            .verify(&keypair.public_key, &ciphertext, &message)
+            .is_err());
+    }
+
+    #[test]
+    fn committed_plaintext() {
+        let mut rng = OsRng;
+        let keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        let commitment_key = CommitmentKey::<RistrettoPoint>::generate(&[0u8]);
+        let message = RScalar::random(&mut rng);
+        let (commitment, commitment_randomness) = commitment_key.commit(&message, &mut rng);
+        let opening = Open {
+            m: message,
+            r: commitment_randomness,
+        };
+        let (ciphertext, randomness) = keypair.public_key.encrypt_return_r(&message, &mut rng);
+
+        let proof = CommittedPlaintextZkp::generate(
+            &keypair.public_key,
+            &commitment_key,
+            &ciphertext,
+            &commitment,
+            &opening,
+            &randomness,
+            &mut rng,
+        );
+        assert!(proof
+            .verify(
+                &keypair.public_key,
+                &commitment_key,
+                &ciphertext,
+                &commitment
+            )
+            .is_ok());
+
+        let other_commitment = commitment + RistrettoPoint::generator();
+        assert!(proof
+            .verify(
+                &keypair.public_key,
+                &commitment_key,
+                &ciphertext,
+                &other_commitment
+            )
+            .is_err());
+        let other_key = CommitmentKey::<RistrettoPoint>::generate(&[1u8]);
+        assert!(proof
+            .verify(&keypair.public_key, &other_key, &ciphertext, &commitment)
+            .is_err());
+
+        // A ciphertext of a different value than the committed one does not verify.
+        let (ciphertext, randomness) = keypair
+            .public_key
+            .encrypt_return_r(&(message + RScalar::one()), &mut rng);
+        let proof = CommittedPlaintextZkp::generate(
+            &keypair.public_key,
+            &commitment_key,
+            &ciphertext,
+            &commitment,
+            &opening,
+            &randomness,
+            &mut rng,
+        );
+        assert!(proof
+            .verify(
+                &keypair.public_key,
+                &commitment_key,
+                &ciphertext,
+                &commitment
+            )
+            .is_err());
+    }
+}
@@ -7,8 +7,9 @@
 //! `NIZK{(pk, C = (C1, C2), D), (sk): D = C1^sk AND pk = g^sk}`
 //!
 //! which is a proof of discrete log equality. We can therefore prove
-//! correct decryption using a proof of discrete log equality.
-use crate::cryptography::dl_equality::DleqZkp;
+//! correct decryption using a proof of discrete log equality. The proof uses the batchable
+//! encoding, so that several proofs can be verified at once.
+use crate::cryptography::dl_equality::{BatchableDleqZkp, DleqStatement};
 use crate::cryptography::elgamal::{HybridCiphertext, SymmetricKey};
 use crate::dkg::procedure_keys::{MemberCommunicationKey, MemberCommunicationPublicKey};
 use crate::errors::ProofError;
@@ -18,7 +19,7 @@ use rand_core::{CryptoRng, RngCore};
 /// Proof of correct decryption.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Zkp<G: PrimeGroupElement> {
-    hybrid_dec_key_proof: DleqZkp<G>,
+    hybrid_dec_key_proof: BatchableDleqZkp<G>,
 }
 
 impl<G> Zkp<G>
@@ -36,14 +37,8 @@ where
     where
         R: CryptoRng + RngCore,
     {
-        let hybrid_dec_key_proof = DleqZkp::generate(
-            &G::generator(),
-            &c.e1,
-            &pk.0.pk,
-            &symmetric_key.group_repr,
-            &sk.0.sk,
-            rng,
-        );
+        let hybrid_dec_key_proof =
+            BatchableDleqZkp::generate(&Self::statement(c, symmetric_key, pk), &sk.0.sk, rng);
         Zkp {
             hybrid_dec_key_proof,
         }
@@ -56,12 +51,41 @@ where
         symmetric_key: &SymmetricKey<G>,
         pk: &MemberCommunicationPublicKey<G>,
     ) -> Result<(), ProofError> {
-        self.hybrid_dec_key_proof.verify(
-            &G::generator(),
-            &c.e1,
-            &pk.0.pk,
-            &symmetric_key.group_repr,
-        )
+        self.hybrid_dec_key_proof
+            .verify(&Self::statement(c, symmetric_key, pk))
+    }
+
+    /// Statement proven by a decryption zero knowledge proof, that `symmetric_key` is the
+    /// decryption key of `c` under the secret key of `pk`.
+    pub fn statement(
+        c: &HybridCiphertext<G>,
+        symmetric_key: &SymmetricKey<G>,
+        pk: &MemberCommunicationPublicKey<G>,
+    ) -> DleqStatement<G> {
+        DleqStatement::new(G::generator(), c.e1, pk.0.pk, symmetric_key.group_repr)
+    }
+
+    /// Verify a batch of decryption zero knowledge proofs, where `proofs[i]` is the proof of
+    /// `statements[i]`, with a single multiscalar multiplication. If the batch fails, the
+    /// proofs need to be verified individually to find the failing ones.
+    pub fn batch_verify<R>(
+        statements: &[DleqStatement<G>],
+        proofs: &[Self],
+        rng: &mut R,
+    ) -> Result<(), ProofError>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let proofs: Vec<BatchableDleqZkp<G>> = proofs
+            .iter()
+            .map(|proof| proof.hybrid_dec_key_proof.clone())
+            .collect();
+        BatchableDleqZkp::batch_verify(statements, &proofs, rng)
+    }
+
+    /// Canonical byte representation of the proof.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        self.hybrid_dec_key_proof.to_bytes()
     }
 }
 
@@ -88,4 +112,32 @@ mod tests {
             .verify(&ciphertext, &decryption_key, &comm_pkey)
             .is_ok())
     }
+
+    #[test]
+    pub fn batch_verification() {
+        let mut r = OsRng;
+
+        let comm_key = MemberCommunicationKey::<RistrettoPoint>::new(&mut r);
+        let comm_pkey = comm_key.to_public();
+
+        let mut statements = Vec::new();
+        let mut proofs = Vec::new();
+        for i in 0..4u8 {
+            let ciphertext = comm_pkey.hybrid_encrypt(&[i; 32], &mut r);
+            let decryption_key = comm_key.0.recover_symmetric_key(&ciphertext);
+            proofs.push(Zkp::generate(
+                &ciphertext,
+                &comm_pkey,
+                &decryption_key,
+                &comm_key,
+                &mut r,
+            ));
+            statements.push(Zkp::statement(&ciphertext, &decryption_key, &comm_pkey));
+        }
+        assert!(Zkp::batch_verify(&statements, &proofs, &mut r).is_ok());
+
+        // A proof for a different decryption key makes the batch fail.
+        statements.swap(0, 1);
+        assert!(Zkp::batch_verify(&statements, &proofs, &mut r).is_err());
+    }
 }
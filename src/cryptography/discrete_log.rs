@@ -0,0 +1,94 @@
+//! Bounded discrete logarithm solver, using the baby-step giant-step algorithm. Given a
+//! point \\( P = m \cdot G \\) with \\( 0 \leq m \leq max \\), it recovers \\( m \\) with
+//! \\( O(\sqrt{max}) \\) group operations, using a precomputed table of
+//! \\( O(\sqrt{max}) \\) points. This is the last step of the decryption of lifted ElGamal
+//! ciphertexts, where the plaintext is encrypted as \\( m \cdot G \\).
+use crate::traits::PrimeGroupElement;
+use std::collections::HashMap;
+
+/// Precomputed table of the baby steps, \\( j \cdot G \\) for \\( 0 \leq j < s \\), where
+/// \\( s = \lceil\sqrt{max + 1}\rceil \\). The table is independent of the keys, so it can be
+/// computed once and reused for every decryption with the same bound.
+#[derive(Clone, Debug)]
+pub struct BabyStepGiantStep<G: PrimeGroupElement> {
+    max: u64,
+    baby_steps: HashMap<Vec<u8>, u64>,
+    giant_step: G,
+}
+
+impl<G: PrimeGroupElement> BabyStepGiantStep<G> {
+    /// Precompute the table to solve discrete logarithms in the range `[0, max]`.
+    pub fn new(max: u64) -> Self {
+        let steps = Self::nr_steps(max);
+
+        let mut baby_steps = HashMap::with_capacity(steps as usize);
+        let mut point = G::zero();
+        for j in 0..steps {
+            baby_steps.insert(point.to_bytes()[..].to_vec(), j);
+            point = point + G::generator();
+        }
+
+        BabyStepGiantStep {
+            max,
+            baby_steps,
+            // after the loop, `point = steps * G`
+            giant_step: -point,
+        }
+    }
+
+    /// Upper bound of the discrete logarithms solved with the table.
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Returns `m` such that `point = m * G`, if `m` is in the range `[0, max]`, or `None`
+    /// otherwise.
+    pub fn solve(&self, point: &G) -> Option<u64> {
+        let steps = Self::nr_steps(self.max);
+
+        // point - i * steps * G, for 0 <= i < steps
+        let mut giant = *point;
+        for i in 0..steps {
+            if let Some(&j) = self.baby_steps.get(&giant.to_bytes()[..]) {
+                let m = i * steps + j;
+                return if m <= self.max { Some(m) } else { None };
+            }
+            giant = giant + self.giant_step;
+        }
+        None
+    }
+
+    // Smallest `s` such that `s * s > max`.
+    fn nr_steps(max: u64) -> u64 {
+        let mut steps = (max as f64).sqrt() as u64;
+        while steps.checked_mul(steps).is_some_and(|square| square <= max) {
+            steps += 1;
+        }
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar as RScalar;
+
+    #[test]
+    fn bounded_discrete_log() {
+        for &max in [0u64, 1, 15, 16, 17, 100].iter() {
+            let table = BabyStepGiantStep::<RistrettoPoint>::new(max);
+            assert_eq!(table.max(), max);
+            for m in 0..=max + 3 {
+                let point = RistrettoPoint::generator() * RScalar::from(m);
+                let expected = if m <= max { Some(m) } else { None };
+                assert_eq!(table.solve(&point), expected);
+            }
+        }
+
+        let table = BabyStepGiantStep::<RistrettoPoint>::new(1 << 20);
+        let point = RistrettoPoint::generator() * RScalar::from(987_654u64);
+        assert_eq!(table.solve(&point), Some(987_654));
+        assert_eq!(table.solve(&-point), None);
+    }
+}
@@ -0,0 +1,184 @@
+//! Non-interactive Zero Knowledge proof of Discrete Logarithm EQuality (DLEQ), encoded with
+//! the announcements instead of the challenge.
+//!
+//! The statement and the proof are the same as in `zkp`, but the proof stores the pair of
+//! announcements `(a1, a2)` and the response `z`. Verification recomputes the challenge `c`
+//! from the announcements, and checks that
+//!
+//! `base_1^z = a1 * point_1^c AND base_2^z = a2 * point_2^c`.
+//!
+//! As these are equations over group elements, the checks of several proofs can be combined
+//! with random weights into a single multiscalar multiplication. This proof is larger than its
+//! compact counterpart, but allows for batch verification of proofs.
+use super::challenge_context::ChallengeContext;
+use crate::errors::ProofError;
+use crate::traits::{PrimeGroupElement, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+/// Statement of a DLEQ proof, `point_1 = base_1^dlog AND point_2 = base_2^dlog`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DleqStatement<G: PrimeGroupElement> {
+    pub base_1: G,
+    pub base_2: G,
+    pub point_1: G,
+    pub point_2: G,
+}
+
+impl<G: PrimeGroupElement> DleqStatement<G> {
+    pub fn new(base_1: G, base_2: G, point_1: G, point_2: G) -> Self {
+        DleqStatement {
+            base_1,
+            base_2,
+            point_1,
+            point_2,
+        }
+    }
+
+    fn challenge(&self, announcement_1: &G, announcement_2: &G) -> G::CorrespondingScalar {
+        let mut challenge_context =
+            ChallengeContext::new(&self.base_1, &self.base_2, &self.point_1, &self.point_2);
+        challenge_context.first_challenge(announcement_1, announcement_2)
+    }
+}
+
+/// DLEQ proof storing the announcements and the response, which allows for batch
+/// verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchableZkp<G: PrimeGroupElement> {
+    announcement_1: G,
+    announcement_2: G,
+    response: G::CorrespondingScalar,
+}
+
+impl<G: PrimeGroupElement> BatchableZkp<G> {
+    /// Generate a batchable DLEQ proof
+    pub fn generate<R>(
+        statement: &DleqStatement<G>,
+        dlog: &G::CorrespondingScalar,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let w = G::CorrespondingScalar::random(rng);
+        let announcement_1 = statement.base_1 * w;
+        let announcement_2 = statement.base_2 * w;
+        let challenge = statement.challenge(&announcement_1, &announcement_2);
+        let response = challenge * *dlog + w;
+
+        BatchableZkp {
+            announcement_1,
+            announcement_2,
+            response,
+        }
+    }
+
+    /// Verify a batchable DLEQ proof
+    pub fn verify(&self, statement: &DleqStatement<G>) -> Result<(), ProofError> {
+        let challenge = statement.challenge(&self.announcement_1, &self.announcement_2);
+        if statement.base_1 * self.response == self.announcement_1 + statement.point_1 * challenge
+            && statement.base_2 * self.response
+                == self.announcement_2 + statement.point_2 * challenge
+        {
+            Ok(())
+        } else {
+            Err(ProofError::ZkpVerificationFailed)
+        }
+    }
+
+    /// Verify a batch of DLEQ proofs, where `proofs[i]` is the proof of `statements[i]`. The
+    /// verification equations of all proofs are combined with random weights, and checked with
+    /// a single multiscalar multiplication. If the batch fails, at least one of the proofs
+    /// is invalid, and proofs need to be verified individually to find the failing ones.
+    pub fn batch_verify<R>(
+        statements: &[DleqStatement<G>],
+        proofs: &[Self],
+        rng: &mut R,
+    ) -> Result<(), ProofError>
+    where
+        R: CryptoRng + RngCore,
+    {
+        if statements.len() != proofs.len() {
+            return Err(ProofError::ZkpVerificationFailed);
+        }
+
+        let mut scalars = Vec::with_capacity(6 * proofs.len());
+        let mut points = Vec::with_capacity(6 * proofs.len());
+        for (statement, proof) in statements.iter().zip(proofs.iter()) {
+            let challenge = statement.challenge(&proof.announcement_1, &proof.announcement_2);
+            let weight_1 = G::CorrespondingScalar::random(rng);
+            let weight_2 = G::CorrespondingScalar::random(rng);
+
+            // weight_1 * (base_1^z - a1 - point_1^c) + weight_2 * (base_2^z - a2 - point_2^c)
+            scalars.extend_from_slice(&[
+                weight_1 * proof.response,
+                -weight_1,
+                -(weight_1 * challenge),
+                weight_2 * proof.response,
+                -weight_2,
+                -(weight_2 * challenge),
+            ]);
+            points.extend_from_slice(&[
+                statement.base_1,
+                proof.announcement_1,
+                statement.point_1,
+                statement.base_2,
+                proof.announcement_2,
+                statement.point_2,
+            ]);
+        }
+
+        if G::vartime_multiscalar_multiplication(scalars, points) == G::zero() {
+            Ok(())
+        } else {
+            Err(ProofError::ZkpVerificationFailed)
+        }
+    }
+
+    /// Canonical byte representation of the proof, `announcement_1 || announcement_2 ||
+    /// response`.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.announcement_1.to_bytes());
+        bytes.extend_from_slice(&self.announcement_2.to_bytes());
+        bytes.extend_from_slice(&self.response.to_bytes());
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blake2::Blake2b;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn batch_verification() {
+        let mut r: OsRng = OsRng;
+
+        let mut statements = Vec::new();
+        let mut proofs = Vec::new();
+        for i in 0..10u8 {
+            let dlog = Scalar::random(&mut r);
+            let base_1 = RistrettoPoint::generator();
+            let base_2 = RistrettoPoint::hash_to_group::<Blake2b>(&[i]);
+            let statement = DleqStatement::new(base_1, base_2, base_1 * dlog, base_2 * dlog);
+            let proof = BatchableZkp::generate(&statement, &dlog, &mut r);
+
+            assert!(proof.verify(&statement).is_ok());
+            statements.push(statement);
+            proofs.push(proof);
+        }
+
+        assert!(BatchableZkp::batch_verify(&statements, &proofs, &mut r).is_ok());
+        assert!(BatchableZkp::<RistrettoPoint>::batch_verify(&[], &[], &mut r).is_ok());
+        assert!(BatchableZkp::batch_verify(&statements[1..], &proofs, &mut r).is_err());
+
+        // A single false statement makes the batch fail.
+        statements[3].point_2 += RistrettoPoint::generator();
+        assert!(proofs[3].verify(&statements[3]).is_err());
+        assert!(BatchableZkp::batch_verify(&statements, &proofs, &mut r).is_err());
+    }
+}
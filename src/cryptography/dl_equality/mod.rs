@@ -1,4 +1,6 @@
+mod batchable;
 mod challenge_context;
 mod zkp;
 
+pub use batchable::{BatchableZkp as BatchableDleqZkp, DleqStatement};
 pub use zkp::Zkp as DleqZkp;
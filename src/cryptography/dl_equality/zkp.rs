@@ -17,7 +17,7 @@ use rand_core::{CryptoRng, RngCore};
 /// Proof of correct decryption.
 /// Note: if the goal is to reduce the size of a proof, it is better to store the challenge
 /// and the response. If on the other hand we want to allow for batch verification of
-/// proofs, we should store the announcements and the response.
+/// proofs, we should store the announcements and the response, as done in `BatchableDleqZkp`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Zkp<G: PrimeGroupElement> {
     challenge: G::CorrespondingScalar,
@@ -4,6 +4,8 @@
 //! corresponding structures. In particular, we use (lifted) ElGamal cryptosystem, and combine with ChaCha
 //! stream cipher to produce a hybrid encryption scheme.
 
+use crate::cryptography::discrete_log::BabyStepGiantStep;
+use crate::cryptography::rerandomization::RerandomizationZkp;
 use crate::traits::{PrimeGroupElement, Scalar};
 use blake2::{Blake2b, Digest};
 use chacha20::cipher::{NewCipher, StreamCipher};
@@ -19,12 +21,40 @@ pub struct PublicKey<G: PrimeGroupElement> {
     pub pk: G,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone)]
 /// ElGamal secret key
 pub struct SecretKey<G: PrimeGroupElement> {
     pub sk: G::CorrespondingScalar,
 }
 
+impl<G: PrimeGroupElement> std::fmt::Debug for SecretKey<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretKey").finish_non_exhaustive()
+    }
+}
+
+impl<G: PrimeGroupElement> PartialEq for SecretKey<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.sk.constant_time_eq(&other.sk)
+    }
+}
+
+impl<G: PrimeGroupElement> Eq for SecretKey<G> {}
+
+#[cfg(feature = "zeroize")]
+impl<G: PrimeGroupElement> zeroize::Zeroize for SecretKey<G> {
+    fn zeroize(&mut self) {
+        self.sk.erase();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<G: PrimeGroupElement> Drop for SecretKey<G> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
 #[derive(Clone)]
 /// ElGamal keypair
 pub struct Keypair<G: PrimeGroupElement> {
@@ -52,11 +82,39 @@ pub struct HybridCiphertext<G: PrimeGroupElement> {
 /// The hybrid encryption scheme uses a group element as a
 /// representation of the symmetric key. This facilitates
 /// its exchange using ElGamal encryption.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone)]
 pub struct SymmetricKey<G: PrimeGroupElement> {
     pub(crate) group_repr: G,
 }
 
+impl<G: PrimeGroupElement> std::fmt::Debug for SymmetricKey<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SymmetricKey").finish_non_exhaustive()
+    }
+}
+
+impl<G: PrimeGroupElement> PartialEq for SymmetricKey<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.group_repr.constant_time_eq(&other.group_repr)
+    }
+}
+
+impl<G: PrimeGroupElement> Eq for SymmetricKey<G> {}
+
+#[cfg(feature = "zeroize")]
+impl<G: PrimeGroupElement> zeroize::Zeroize for SymmetricKey<G> {
+    fn zeroize(&mut self) {
+        self.group_repr.erase();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<G: PrimeGroupElement> Drop for SymmetricKey<G> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
 impl<G: PrimeGroupElement> PublicKey<G> {
     /// Given a `message` represented as a group element, return a ciphertext.
     pub(crate) fn encrypt_point<R>(&self, message: &G, rng: &mut R) -> Ciphertext<G>
@@ -91,23 +149,23 @@ impl<G: PrimeGroupElement> PublicKey<G> {
         randomness: &G::CorrespondingScalar,
     ) -> Ciphertext<G> {
         Ciphertext {
-            e1: G::generator() * *randomness,
+            e1: G::generator_mul(randomness),
             e2: (self.pk * *randomness) + *message,
         }
     }
 
     /// Given a `message` represented as a `Scalar`, return a ciphertext using the
     /// "lifted ElGamal" mechanism. Mainly, return (r * G; `message` * G + r * `self`)
-    pub(crate) fn encrypt<R>(&self, message: &G::CorrespondingScalar, rng: &mut R) -> Ciphertext<G>
+    pub fn encrypt<R>(&self, message: &G::CorrespondingScalar, rng: &mut R) -> Ciphertext<G>
     where
         R: RngCore + CryptoRng,
     {
-        self.encrypt_point(&(G::generator() * *message), rng)
+        self.encrypt_point(&G::generator_mul(message), rng)
     }
 
     /// Given a `message` represented as a `Scalar`, return a ciphertext and return
     /// the randomness used.
-    pub(crate) fn encrypt_return_r<R>(
+    pub fn encrypt_return_r<R>(
         &self,
         message: &G::CorrespondingScalar,
         rng: &mut R,
@@ -115,19 +173,19 @@ impl<G: PrimeGroupElement> PublicKey<G> {
     where
         R: RngCore + CryptoRng,
     {
-        self.encrypt_point_return_r(&(G::generator() * *message), rng)
+        self.encrypt_point_return_r(&G::generator_mul(message), rng)
     }
 
     /// Given a `message` represented as a `Scalar`, and some value used as `randomness`,
     /// return the corresponding ciphertext. This function should only be called when the
     /// randomness value is not random (e.g. verification procedure of the unit vector ZKP).
     /// Otherwise, `encrypt_point` should be used.
-    pub(crate) fn encrypt_with_r(
+    pub fn encrypt_with_r(
         &self,
         message: &G::CorrespondingScalar,
         randomness: &G::CorrespondingScalar,
     ) -> Ciphertext<G> {
-        self.encrypt_point_with_r(&(G::generator() * *message), randomness)
+        self.encrypt_point_with_r(&G::generator_mul(message), randomness)
     }
 
     /// Given a `message` passed as bytes, encrypt it using hybrid encryption.
@@ -136,10 +194,22 @@ impl<G: PrimeGroupElement> PublicKey<G> {
         R: RngCore + CryptoRng,
     {
         let encryption_randomness = G::CorrespondingScalar::random(rng);
+        self.hybrid_encrypt_with_r(message, &encryption_randomness)
+    }
+
+    /// Given a `message` passed as bytes, and some value used as `encryption_randomness`,
+    /// encrypt it using hybrid encryption. This function should only be called when the
+    /// randomness needs to be sampled ahead of the encryption (e.g. to encrypt several messages
+    /// in parallel). Otherwise, `hybrid_encrypt` should be used.
+    pub(crate) fn hybrid_encrypt_with_r(
+        &self,
+        message: &[u8],
+        encryption_randomness: &G::CorrespondingScalar,
+    ) -> HybridCiphertext<G> {
         let symmetric_key = SymmetricKey {
-            group_repr: self.pk * encryption_randomness,
+            group_repr: self.pk * *encryption_randomness,
         };
-        let e1 = G::generator() * encryption_randomness;
+        let e1 = G::generator_mul(encryption_randomness);
         let e2 = symmetric_key.process(message).into_boxed_slice();
         HybridCiphertext { e1, e2 }
     }
@@ -151,6 +221,12 @@ impl<G: PrimeGroupElement> SecretKey<G> {
         Self { sk }
     }
 
+    /// Debug representation of the key which, unlike `Debug`, includes the secret scalar.
+    /// Handle the output with care, as it discloses the key.
+    pub fn danger_debug(&self) -> String {
+        format!("SecretKey {{ sk: {:?} }}", self.sk)
+    }
+
     /// Decrypt ElGamal `Ciphertext` = (`cipher`.e1, `cipher`.e2), by computing
     /// `cipher`.e2 - `self` * `cipher`.e1. This returns the plaintext respresented
     /// as a `PrimeGroupElement`.
@@ -158,6 +234,19 @@ impl<G: PrimeGroupElement> SecretKey<G> {
         (cipher.e1 * (-self.sk)) + cipher.e2
     }
 
+    /// Decrypt a lifted ElGamal `Ciphertext` of a small scalar, by decrypting the point
+    /// `message` * G and solving its discrete logarithm with the precomputed `table`. Returns
+    /// `None` if the message is larger than the bound of the table. Decryption shares combined
+    /// by a threshold of members result in the point `message` * G as well, which can be solved
+    /// directly with `BabyStepGiantStep::solve`.
+    pub fn decrypt_scalar_bounded(
+        &self,
+        cipher: &Ciphertext<G>,
+        table: &BabyStepGiantStep<G>,
+    ) -> Option<u64> {
+        table.solve(&self.decrypt_point(cipher))
+    }
+
     pub(crate) fn recover_symmetric_key(
         &self,
         ciphertext: &HybridCiphertext<G>,
@@ -176,6 +265,12 @@ impl<G: PrimeGroupElement> SecretKey<G> {
 }
 
 impl<G: PrimeGroupElement> SymmetricKey<G> {
+    /// Debug representation of the key which, unlike `Debug`, includes its group
+    /// representation. Handle the output with care, as it discloses the key.
+    pub fn danger_debug(&self) -> String {
+        format!("SymmetricKey {{ group_repr: {:?} }}", self.group_repr)
+    }
+
     // Initialise encryption, by hashing the group element
     fn initialise_encryption(&self) -> ChaCha20 {
         let h = Blake2b::new().chain(&self.group_repr.to_bytes()).finalize();
@@ -197,7 +292,7 @@ impl<G: PrimeGroupElement> Keypair<G> {
     #[allow(dead_code)]
     pub fn from_secretkey(secret_key: SecretKey<G>) -> Self {
         let public_key = PublicKey {
-            pk: G::generator() * secret_key.sk,
+            pk: G::generator_mul(&secret_key.sk),
         };
         Keypair {
             secret_key,
@@ -208,7 +303,7 @@ impl<G: PrimeGroupElement> Keypair<G> {
     /// Generate a keypair for encryption
     pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Keypair<G> {
         let sk = G::CorrespondingScalar::random(rng);
-        let pk = G::generator() * sk;
+        let pk = G::generator_mul(&sk);
         Keypair {
             secret_key: SecretKey::<G> { sk },
             public_key: PublicKey::<G> { pk },
@@ -228,6 +323,43 @@ impl<G: PrimeGroupElement> Ciphertext<G> {
     pub fn elements(&self) -> (&G, &G) {
         (&self.e1, &self.e2)
     }
+
+    /// Re-randomise the ciphertext, encrypted under `public_key`, by adding an encryption of
+    /// zero. The result decrypts to the same plaintext, but is unlinkable to the original
+    /// ciphertext without the secret key.
+    pub fn rerandomize<R>(&self, public_key: &PublicKey<G>, rng: &mut R) -> Self
+    where
+        R: RngCore + CryptoRng,
+    {
+        let randomness = G::CorrespondingScalar::random(rng);
+        self.rerandomize_with_r(public_key, &randomness)
+    }
+
+    /// Re-randomise the ciphertext with the given `randomness`, i.e. return
+    /// (e1 + r * G; e2 + r * `public_key`).
+    pub fn rerandomize_with_r(
+        &self,
+        public_key: &PublicKey<G>,
+        randomness: &G::CorrespondingScalar,
+    ) -> Self {
+        self + public_key.encrypt_point_with_r(&G::zero(), randomness)
+    }
+
+    /// Re-randomise the ciphertext, and return a proof that the result is a re-randomisation
+    /// of `self` under `public_key`.
+    pub fn rerandomize_with_proof<R>(
+        &self,
+        public_key: &PublicKey<G>,
+        rng: &mut R,
+    ) -> (Self, RerandomizationZkp<G>)
+    where
+        R: RngCore + CryptoRng,
+    {
+        let randomness = G::CorrespondingScalar::random(rng);
+        let rerandomized = self.rerandomize_with_r(public_key, &randomness);
+        let proof = RerandomizationZkp::generate(public_key, self, &rerandomized, &randomness, rng);
+        (rerandomized, proof)
+    }
 }
 
 impl<'a, 'b, G: PrimeGroupElement> Add<&'b Ciphertext<G>> for &'a Ciphertext<G> {
@@ -326,6 +458,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn encrypt_decrypt_bounded() {
+        let mut rng = OsRng;
+        let keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        let table = BabyStepGiantStep::new(1000);
+
+        for &m in [0u64, 1, 42, 1000].iter() {
+            let cipher = keypair.public_key.encrypt(&RScalar::from(m), &mut rng);
+            assert_eq!(
+                keypair.secret_key.decrypt_scalar_bounded(&cipher, &table),
+                Some(m)
+            );
+        }
+
+        // The sum of encrypted values is decrypted as long as it is within the bound.
+        let cipher = keypair.public_key.encrypt(&RScalar::from(600u64), &mut rng);
+        assert_eq!(
+            keypair
+                .secret_key
+                .decrypt_scalar_bounded(&(cipher + cipher), &table),
+            None
+        );
+        let other = keypair.public_key.encrypt(&RScalar::from(400u64), &mut rng);
+        assert_eq!(
+            keypair
+                .secret_key
+                .decrypt_scalar_bounded(&(cipher + other), &table),
+            Some(1000)
+        );
+    }
+
     #[test]
     fn symmetric_encrypt_decrypt() {
         let mut rng = OsRng;
@@ -340,6 +503,32 @@ mod tests {
         assert_eq!(&m[..], &result[..])
     }
 
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_secret_key() {
+        use zeroize::Zeroize;
+
+        let mut rng = OsRng;
+        let mut k = SecretKey::<RistrettoPoint>::generate(&mut rng);
+        k.zeroize();
+
+        assert_eq!(k.sk, RScalar::zero());
+    }
+
+    #[test]
+    fn redacted_secret_key() {
+        let mut rng = OsRng;
+        let k = SecretKey::<RistrettoPoint>::generate(&mut rng);
+        let other_k = SecretKey::<RistrettoPoint>::generate(&mut rng);
+
+        let secret = format!("{:?}", k.sk);
+        assert!(!format!("{:?}", k).contains(&secret));
+        assert!(k.danger_debug().contains(&secret));
+
+        assert_eq!(k, k.clone());
+        assert_ne!(k, other_k);
+    }
+
     #[test]
     fn linear_ops_ctxts() {
         let mut rng = OsRng;
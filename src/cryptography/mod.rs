@@ -1,5 +1,13 @@
 //! Generic cryptographic procedures used in DKG.
+pub mod ciphertext_vector;
 pub mod commitment;
+pub mod correct_encryption;
 pub mod correct_hybrid_decryption_key;
-mod dl_equality;
+pub mod discrete_log;
+pub mod dl_equality;
 pub mod elgamal;
+pub mod rerandomization;
+pub mod schnorr;
+pub mod shuffle;
+pub mod signature;
+pub mod unit_vector;
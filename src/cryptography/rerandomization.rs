@@ -0,0 +1,104 @@
+//! Non-interactive Zero Knowledge proof that an ElGamal ciphertext is a re-randomisation of
+//! another, i.e. that \\( (e_1', e_2') = (e_1 + g^s, e_2 + pk^s) \\) for some randomness
+//! \\( s \\), which is not revealed. This is the DLEQ proof
+//!
+//! `NIZK{(g, pk, e1' - e1, e2' - e2), (s): e1' - e1 = g^s AND e2' - e2 = pk^s}`
+//!
+//! so the re-randomised ciphertext decrypts to the same plaintext as the original one.
+use crate::cryptography::dl_equality::DleqZkp;
+use crate::cryptography::elgamal::{Ciphertext, PublicKey};
+use crate::errors::ProofError;
+use crate::traits::PrimeGroupElement;
+use rand_core::{CryptoRng, RngCore};
+
+/// Proof of correct re-randomisation of a ciphertext.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RerandomizationZkp<G: PrimeGroupElement>(DleqZkp<G>);
+
+impl<G: PrimeGroupElement> RerandomizationZkp<G> {
+    /// Generate a proof that `rerandomized` is the re-randomisation of `original` under
+    /// `public_key`, with `randomness`.
+    pub fn generate<R>(
+        public_key: &PublicKey<G>,
+        original: &Ciphertext<G>,
+        rerandomized: &Ciphertext<G>,
+        randomness: &G::CorrespondingScalar,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let difference = rerandomized - original;
+        RerandomizationZkp(DleqZkp::generate(
+            &G::generator(),
+            &public_key.pk,
+            &difference.e1,
+            &difference.e2,
+            randomness,
+            rng,
+        ))
+    }
+
+    /// Verify that `rerandomized` is a re-randomisation of `original` under `public_key`.
+    pub fn verify(
+        &self,
+        public_key: &PublicKey<G>,
+        original: &Ciphertext<G>,
+        rerandomized: &Ciphertext<G>,
+    ) -> Result<(), ProofError> {
+        let difference = rerandomized - original;
+        self.0.verify(
+            &G::generator(),
+            &public_key.pk,
+            &difference.e1,
+            &difference.e2,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptography::elgamal::Keypair;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar as RScalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn rerandomization() {
+        let mut rng = OsRng;
+        let keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        let message = RScalar::from(42u64);
+        let ciphertext = keypair.public_key.encrypt(&message, &mut rng);
+
+        let (rerandomized, proof) =
+            ciphertext.rerandomize_with_proof(&keypair.public_key, &mut rng);
+        assert_ne!(rerandomized, ciphertext);
+        assert_eq!(
+            keypair.secret_key.decrypt_point(&rerandomized),
+            RistrettoPoint::generator() * message
+        );
+        assert!(proof
+            .verify(&keypair.public_key, &ciphertext, &rerandomized)
+            .is_ok());
+
+        // A re-randomisation without proof is also a valid re-randomisation.
+        assert_eq!(
+            keypair
+                .secret_key
+                .decrypt_point(&rerandomized.rerandomize(&keypair.public_key, &mut rng)),
+            RistrettoPoint::generator() * message
+        );
+
+        // The proof does not verify for a ciphertext of a different plaintext, nor under a
+        // different key.
+        let other_ciphertext = rerandomized + keypair.public_key.encrypt(&RScalar::one(), &mut rng);
+        assert!(proof
+            .verify(&keypair.public_key, &ciphertext, &other_ciphertext)
+            .is_err());
+        let other_keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        assert!(proof
+            .verify(&other_keypair.public_key, &ciphertext, &rerandomized)
+            .is_err());
+    }
+}
@@ -0,0 +1,113 @@
+//! Non-interactive Zero Knowledge proof of knowledge of a discrete logarithm, i.e. the
+//! Schnorr identification protocol with the Fiat-Shamir transform.
+//!
+//! The proof is the following:
+//!
+//! `NIZK{(base, point), (dlog): point = base^dlog}`
+//!
+//! The challenge is additionally bound to a `context`, so that a proof generated in one
+//! context (e.g. by one member in one session) cannot be replayed in another.
+use crate::errors::ProofError;
+use crate::traits::{PrimeGroupElement, Scalar};
+use blake2::Blake2b;
+use rand_core::{CryptoRng, RngCore};
+
+/// Proof of knowledge of a discrete logarithm, storing the challenge and the response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zkp<G: PrimeGroupElement> {
+    challenge: G::CorrespondingScalar,
+    response: G::CorrespondingScalar,
+}
+
+impl<G: PrimeGroupElement> Zkp<G> {
+    /// Generate a proof of knowledge of `dlog`, such that `point = base^dlog`.
+    pub fn generate<R>(
+        base: &G,
+        point: &G,
+        dlog: &G::CorrespondingScalar,
+        context: &[u8],
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let w = G::CorrespondingScalar::random(rng);
+        let announcement = *base * w;
+        let challenge = Self::challenge(base, point, &announcement, context);
+        let response = challenge * *dlog + w;
+
+        Zkp {
+            challenge,
+            response,
+        }
+    }
+
+    /// Verify a proof of knowledge of the discrete logarithm of `point` with respect to
+    /// `base`, in the given `context`.
+    pub fn verify(&self, base: &G, point: &G, context: &[u8]) -> Result<(), ProofError> {
+        let announcement = *base * self.response - *point * self.challenge;
+        // no need for constant time equality because of the hash in challenge()
+        if Self::challenge(base, point, &announcement, context) == self.challenge {
+            Ok(())
+        } else {
+            Err(ProofError::ZkpVerificationFailed)
+        }
+    }
+
+    /// Canonical byte representation of the proof, `challenge || response`.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.challenge.to_bytes());
+        bytes.extend_from_slice(&self.response.to_bytes());
+        bytes
+    }
+
+    fn challenge(base: &G, point: &G, announcement: &G, context: &[u8]) -> G::CorrespondingScalar {
+        let mut ctx: Vec<u8> = Vec::new();
+        ctx.extend_from_slice(&base.to_bytes());
+        ctx.extend_from_slice(&point.to_bytes());
+        ctx.extend_from_slice(&(context.len() as u64).to_le_bytes());
+        ctx.extend_from_slice(context);
+        ctx.extend_from_slice(&announcement.to_bytes());
+
+        G::CorrespondingScalar::hash_to_scalar::<Blake2b>(&ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blake2::Blake2b;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn proof_of_knowledge() {
+        let mut r: OsRng = OsRng;
+
+        let dlog = Scalar::random(&mut r);
+        let base = RistrettoPoint::hash_to_group::<Blake2b>(&[0u8]);
+        let point = base * dlog;
+
+        let proof = Zkp::<RistrettoPoint>::generate(&base, &point, &dlog, b"context", &mut r);
+        assert!(proof.verify(&base, &point, b"context").is_ok());
+
+        // The proof is bound to the statement and to the context.
+        assert!(proof.verify(&base, &(point + base), b"context").is_err());
+        assert!(proof
+            .verify(&RistrettoPoint::generator(), &point, b"context")
+            .is_err());
+        assert!(proof.verify(&base, &point, b"other context").is_err());
+
+        // A proof with a different witness does not verify.
+        let forged = Zkp::<RistrettoPoint>::generate(
+            &base,
+            &point,
+            &(dlog + Scalar::one()),
+            b"context",
+            &mut r,
+        );
+        assert!(forged.verify(&base, &point, b"context").is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use crate::cryptography::commitment::VectorCommitmentKey;
+use crate::cryptography::elgamal::{Ciphertext, PublicKey};
+use crate::traits::{PrimeGroupElement, Scalar};
+use blake2::Blake2b;
+
+/// Challenge context for the proof of a shuffle. The common reference string are the public
+/// key of the encryption and the commitment key, and the statement consists of the input and
+/// output ciphertexts. The prover first commits to the permutation, which defines the
+/// `batch_challenges` used to combine the ciphertexts, and then to the commitment chain and
+/// the announcements, which define the final challenge.
+#[derive(Debug, Clone)]
+pub struct ChallengeContext(Vec<u8>);
+
+impl ChallengeContext {
+    /// Initialise the challenge context, by including the common reference string, the full
+    /// statement, and the commitments to the permutation
+    pub(crate) fn new<G: PrimeGroupElement>(
+        public_key: &PublicKey<G>,
+        commitment_key: &VectorCommitmentKey<G>,
+        inputs: &[Ciphertext<G>],
+        outputs: &[Ciphertext<G>],
+        permutation_commitments: &[G],
+    ) -> Self {
+        let mut ctx: Vec<u8> = Vec::new();
+        ctx.extend_from_slice(&public_key.pk.to_bytes());
+        ctx.extend_from_slice(&(inputs.len() as u64).to_le_bytes());
+        ctx.extend_from_slice(&commitment_key.h().to_bytes());
+        for generator in &commitment_key.generators()[..inputs.len()] {
+            ctx.extend_from_slice(&generator.to_bytes());
+        }
+        for ciphertext in inputs.iter().chain(outputs.iter()) {
+            ctx.extend_from_slice(&ciphertext.e1.to_bytes());
+            ctx.extend_from_slice(&ciphertext.e2.to_bytes());
+        }
+        for commitment in permutation_commitments {
+            ctx.extend_from_slice(&commitment.to_bytes());
+        }
+
+        ChallengeContext(ctx)
+    }
+
+    /// Generation of the `nr_challenges` challenges, `u_i`, which combine the ciphertexts.
+    pub(crate) fn batch_challenges<S: Scalar>(&self, nr_challenges: usize) -> Vec<S> {
+        (0..nr_challenges)
+            .map(|i| {
+                let mut ctx = self.0.clone();
+                ctx.extend_from_slice(&(i as u64).to_le_bytes());
+                S::hash_to_scalar::<Blake2b>(&ctx)
+            })
+            .collect()
+    }
+
+    /// Generation of the final `challenge`, after the commitment chain and the announcements
+    /// are "sent".
+    pub(crate) fn challenge<G: PrimeGroupElement>(
+        &mut self,
+        chain_commitments: &[G],
+        announcements: &[G],
+    ) -> G::CorrespondingScalar {
+        for point in chain_commitments.iter().chain(announcements.iter()) {
+            self.0.extend_from_slice(&point.to_bytes());
+        }
+
+        <G as PrimeGroupElement>::CorrespondingScalar::hash_to_scalar::<Blake2b>(&self.0)
+    }
+}
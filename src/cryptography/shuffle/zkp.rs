@@ -0,0 +1,478 @@
+//! Verifiable shuffle of ElGamal ciphertexts, with the proof of
+//! [Terelius and Wikström](https://eprint.iacr.org/2010/342.pdf), following the description
+//! of [Haenni et al.](https://e-voting.bfh.ch/app/download/7833162361/PS2017.pdf).
+//!
+//! The shuffle permutes a list of ciphertexts, `e_1, ..., e_N`, and re-randomises each of
+//! them, `e'_i = e_{ψ(i)} + Enc(0; r'_i)`. The proof is the following:
+//!
+//! `NIZK{(pk, e, e'), (ψ, r'): e'_i = e_{ψ(i)} + Enc_pk(0; r'_i)}`
+//!
+//! The prover commits to the columns of the permutation matrix with the Pedersen vector
+//! commitments of a `VectorCommitmentKey`, \\( c_{ψ(i)} = h^{r_{ψ(i)}} g_i \\). Given
+//! challenges `u_1, ..., u_N`, it then proves that the committed matrix is a permutation
+//! matrix, with a chain of commitments to the products of the permuted challenges,
+//! \\( \hat{c}_i = g^{\hat{r}_i} \hat{c}_{i-1}^{u_{ψ(i)}} \\), and that
+//! \\( \sum_i u_i e_i \\) and \\( \sum_i u_{ψ(i)} e'_i \\) are encryptions of the same
+//! plaintext.
+//!
+//! Note that the roles of the generators differ from the notation of Haenni et al.: the
+//! message generators `g_1, ..., g_N` of the key play the role of their `h_1, ..., h_N`, and
+//! the randomness of the permutation commitments uses the generator `h` of the key instead
+//! of the group generator `g`. The commitment chain is as in Haenni et al., with the group
+//! generator `g` for the randomness and \\( \hat{c}_0 = h \\).
+use super::challenge_context::ChallengeContext;
+use crate::cryptography::commitment::VectorCommitmentKey;
+use crate::cryptography::elgamal::{Ciphertext, PublicKey};
+use crate::errors::ProofError;
+use crate::traits::{PrimeGroupElement, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+/// Proof of a shuffle of ciphertexts. It consists of the commitments to the permutation and
+/// the commitment chain, the challenge, and the responses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zkp<G: PrimeGroupElement> {
+    permutation_commitments: Vec<G>,
+    chain_commitments: Vec<G>,
+    challenge: G::CorrespondingScalar,
+    responses: [G::CorrespondingScalar; 4],
+    chain_responses: Vec<G::CorrespondingScalar>,
+    permutation_responses: Vec<G::CorrespondingScalar>,
+}
+
+/// Shuffle `ciphertexts`, encrypted under `public_key`, with a random permutation, and
+/// re-randomise them. Returns the shuffled ciphertexts, and the proof of the shuffle.
+///
+/// # Panics
+///
+/// Panics if `ciphertexts` is empty, or if it is longer than `commitment_key`.
+pub fn shuffle<G, R>(
+    public_key: &PublicKey<G>,
+    commitment_key: &VectorCommitmentKey<G>,
+    ciphertexts: &[Ciphertext<G>],
+    rng: &mut R,
+) -> (Vec<Ciphertext<G>>, Zkp<G>)
+where
+    G: PrimeGroupElement,
+    R: CryptoRng + RngCore,
+{
+    let permutation = random_permutation(ciphertexts.len(), rng);
+    let randomness: Vec<G::CorrespondingScalar> = (0..ciphertexts.len())
+        .map(|_| G::CorrespondingScalar::random(rng))
+        .collect();
+    let shuffled: Vec<Ciphertext<G>> = permutation
+        .iter()
+        .zip(randomness.iter())
+        .map(|(&j, r)| ciphertexts[j].rerandomize_with_r(public_key, r))
+        .collect();
+
+    let proof = Zkp::generate(
+        public_key,
+        commitment_key,
+        ciphertexts,
+        &shuffled,
+        &permutation,
+        &randomness,
+        rng,
+    );
+    (shuffled, proof)
+}
+
+impl<G: PrimeGroupElement> Zkp<G> {
+    /// Generate a proof that `outputs` is a shuffle of `inputs`, where
+    /// `outputs[i] = inputs[permutation[i]].rerandomize_with_r(public_key, &randomness[i])`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` is empty or longer than `commitment_key`, if `permutation` is not a
+    /// permutation of the indices of `inputs`, or if `outputs` and `randomness` do not have
+    /// the same length as `inputs`.
+    pub fn generate<R>(
+        public_key: &PublicKey<G>,
+        commitment_key: &VectorCommitmentKey<G>,
+        inputs: &[Ciphertext<G>],
+        outputs: &[Ciphertext<G>],
+        permutation: &[usize],
+        randomness: &[G::CorrespondingScalar],
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let size = inputs.len();
+        assert!(size > 0);
+        assert_eq!(outputs.len(), size);
+        assert_eq!(randomness.len(), size);
+        assert!(size <= commitment_key.len());
+        assert!(is_permutation(permutation, size));
+        let generators = &commitment_key.generators()[..size];
+
+        // Commitment to the columns of the permutation matrix, c_{ψ(i)} = h^{r_{ψ(i)}} g_i
+        let permutation_randomness = random_scalars::<G, R>(size, rng);
+        let mut permutation_commitments = vec![G::zero(); size];
+        for (i, &j) in permutation.iter().enumerate() {
+            permutation_commitments[j] =
+                commitment_key.h_mul(&permutation_randomness[j]) + generators[i];
+        }
+
+        let mut challenge_context = ChallengeContext::new(
+            public_key,
+            commitment_key,
+            inputs,
+            outputs,
+            &permutation_commitments,
+        );
+        let batch_challenges: Vec<G::CorrespondingScalar> =
+            challenge_context.batch_challenges(size);
+        let permuted_challenges: Vec<G::CorrespondingScalar> =
+            permutation.iter().map(|&j| batch_challenges[j]).collect();
+
+        // Commitment chain, ĉ_i = g^{r̂_i} ĉ_{i-1}^{u_{ψ(i)}}, with ĉ_0 = h
+        let chain_randomness = random_scalars::<G, R>(size, rng);
+        let mut chain_commitments = Vec::with_capacity(size);
+        let mut previous = commitment_key.h();
+        for (r, u) in chain_randomness.iter().zip(permuted_challenges.iter()) {
+            previous = G::generator_mul(r) + previous * *u;
+            chain_commitments.push(previous);
+        }
+
+        let blinders = random_scalars::<G, R>(4, rng);
+        let chain_blinders = random_scalars::<G, R>(size, rng);
+        let permutation_blinders = random_scalars::<G, R>(size, rng);
+        // The blinders are secret, so the announcements are computed in constant time.
+        let mut announcements = vec![
+            commitment_key.h_mul(&blinders[0]),
+            G::generator_mul(&blinders[1]),
+            commitment_key.h_mul(&blinders[2])
+                + multiscalar_multiplication(&permutation_blinders, generators.iter().copied()),
+            multiscalar_multiplication(
+                &permutation_blinders,
+                outputs.iter().map(|ciphertext| ciphertext.e1),
+            ) - G::generator_mul(&blinders[3]),
+            multiscalar_multiplication(
+                &permutation_blinders,
+                outputs.iter().map(|ciphertext| ciphertext.e2),
+            ) - public_key.pk * blinders[3],
+        ];
+        announcements.extend(
+            chain_blinders
+                .iter()
+                .zip(permutation_blinders.iter())
+                .zip(std::iter::once(&commitment_key.h()).chain(chain_commitments.iter()))
+                .map(|((w_chain, w_permutation), previous)| {
+                    G::generator_mul(w_chain) + *previous * *w_permutation
+                }),
+        );
+        let challenge = challenge_context.challenge(&chain_commitments, &announcements);
+
+        // v_i = prod_{k > i} u_{ψ(k)}, the exponent of r̂_i in ĉ_N
+        let mut chain_exponents = vec![G::CorrespondingScalar::one(); size];
+        for i in (0..size - 1).rev() {
+            chain_exponents[i] = chain_exponents[i + 1] * permuted_challenges[i + 1];
+        }
+        let witnesses = [
+            sum(permutation_randomness.iter().copied()),
+            inner_product(&chain_randomness, &chain_exponents),
+            inner_product(&permutation_randomness, &batch_challenges),
+            inner_product(randomness, &permuted_challenges),
+        ];
+        let mut responses = [G::CorrespondingScalar::zero(); 4];
+        for (response, (blinder, witness)) in responses
+            .iter_mut()
+            .zip(blinders.iter().zip(witnesses.iter()))
+        {
+            *response = *blinder + challenge * *witness;
+        }
+
+        Zkp {
+            permutation_commitments,
+            chain_commitments,
+            challenge,
+            responses,
+            chain_responses: chain_blinders
+                .iter()
+                .zip(chain_randomness.iter())
+                .map(|(w, r)| *w + challenge * *r)
+                .collect(),
+            permutation_responses: permutation_blinders
+                .iter()
+                .zip(permuted_challenges.iter())
+                .map(|(w, u)| *w + challenge * *u)
+                .collect(),
+        }
+    }
+
+    /// Verify that `outputs` is a shuffle of `inputs`, encrypted under `public_key`.
+    pub fn verify(
+        &self,
+        public_key: &PublicKey<G>,
+        commitment_key: &VectorCommitmentKey<G>,
+        inputs: &[Ciphertext<G>],
+        outputs: &[Ciphertext<G>],
+    ) -> Result<(), ProofError> {
+        let size = inputs.len();
+        if size == 0
+            || outputs.len() != size
+            || self.permutation_commitments.len() != size
+            || self.chain_commitments.len() != size
+            || self.chain_responses.len() != size
+            || self.permutation_responses.len() != size
+            || commitment_key.len() < size
+        {
+            return Err(ProofError::ZkpVerificationFailed);
+        }
+        let generators = &commitment_key.generators()[..size];
+
+        let mut challenge_context = ChallengeContext::new(
+            public_key,
+            commitment_key,
+            inputs,
+            outputs,
+            &self.permutation_commitments,
+        );
+        let batch_challenges: Vec<G::CorrespondingScalar> =
+            challenge_context.batch_challenges(size);
+        let [s_1, s_2, s_3, s_4] = self.responses;
+        let c = self.challenge;
+
+        // c̄ = prod_i c_i / prod_i g_i, ĉ = ĉ_N / h^{prod_i u_i}, c̃ = prod_i c_i^{u_i}
+        let permutation_sum =
+            sum(self.permutation_commitments.iter().copied()) - sum(generators.iter().copied());
+        let challenges_product = batch_challenges
+            .iter()
+            .fold(G::CorrespondingScalar::one(), |product, u| product * *u);
+        let chain_end = self.chain_commitments[size - 1] - commitment_key.h() * challenges_product;
+        let weighted_commitments = G::vartime_multiscalar_multiplication(
+            batch_challenges.iter().copied(),
+            self.permutation_commitments.iter().copied(),
+        );
+        let weighted_inputs = [
+            G::vartime_multiscalar_multiplication(
+                batch_challenges.iter().copied(),
+                inputs.iter().map(|ciphertext| ciphertext.e1),
+            ),
+            G::vartime_multiscalar_multiplication(
+                batch_challenges.iter().copied(),
+                inputs.iter().map(|ciphertext| ciphertext.e2),
+            ),
+        ];
+
+        let mut announcements = vec![
+            commitment_key.h_mul(&s_1) - permutation_sum * c,
+            G::generator_mul(&s_2) - chain_end * c,
+            commitment_key.h_mul(&s_3)
+                + G::vartime_multiscalar_multiplication(
+                    self.permutation_responses.iter().copied(),
+                    generators.iter().copied(),
+                )
+                - weighted_commitments * c,
+            G::vartime_multiscalar_multiplication(
+                self.permutation_responses.iter().copied(),
+                outputs.iter().map(|ciphertext| ciphertext.e1),
+            ) - G::generator_mul(&s_4)
+                - weighted_inputs[0] * c,
+            G::vartime_multiscalar_multiplication(
+                self.permutation_responses.iter().copied(),
+                outputs.iter().map(|ciphertext| ciphertext.e2),
+            ) - public_key.pk * s_4
+                - weighted_inputs[1] * c,
+        ];
+        announcements.extend(
+            self.chain_responses
+                .iter()
+                .zip(self.permutation_responses.iter())
+                .zip(std::iter::once(&commitment_key.h()).chain(self.chain_commitments.iter()))
+                .zip(self.chain_commitments.iter())
+                .map(|(((s_chain, s_permutation), previous), current)| {
+                    G::generator_mul(s_chain) + *previous * *s_permutation - *current * c
+                }),
+        );
+
+        let challenge = challenge_context.challenge(&self.chain_commitments, &announcements);
+        // no need for constant time equality because of the hash in challenge()
+        if challenge == self.challenge {
+            Ok(())
+        } else {
+            Err(ProofError::ZkpVerificationFailed)
+        }
+    }
+}
+
+// Uniformly random permutation of `0..size`, with the Fisher-Yates shuffle.
+fn random_permutation<R: CryptoRng + RngCore>(size: usize, rng: &mut R) -> Vec<usize> {
+    let mut permutation: Vec<usize> = (0..size).collect();
+    for i in (1..size).rev() {
+        // Rejection sampling of an index in `0..=i`, to avoid the bias of the modulo.
+        let bound = i as u64 + 1;
+        let zone = u64::MAX - u64::MAX % bound;
+        let j = loop {
+            let sample = rng.next_u64();
+            if sample < zone {
+                break (sample % bound) as usize;
+            }
+        };
+        permutation.swap(i, j);
+    }
+    permutation
+}
+
+fn is_permutation(permutation: &[usize], size: usize) -> bool {
+    let mut seen = vec![false; size];
+    permutation.len() == size
+        && permutation
+            .iter()
+            .all(|&j| j < size && !std::mem::replace(&mut seen[j], true))
+}
+
+fn random_scalars<G, R>(size: usize, rng: &mut R) -> Vec<G::CorrespondingScalar>
+where
+    G: PrimeGroupElement,
+    R: CryptoRng + RngCore,
+{
+    (0..size)
+        .map(|_| G::CorrespondingScalar::random(rng))
+        .collect()
+}
+
+fn sum<T: std::ops::Add<Output = T>>(mut elements: impl Iterator<Item = T>) -> T {
+    let first = elements.next().expect("at least one element");
+    elements.fold(first, |sum, element| sum + element)
+}
+
+// Constant-time multiscalar multiplication, for the secret scalars of the prover. The
+// verifier uses `vartime_multiscalar_multiplication` instead, as its inputs are public.
+fn multiscalar_multiplication<G: PrimeGroupElement>(
+    scalars: &[G::CorrespondingScalar],
+    points: impl Iterator<Item = G>,
+) -> G {
+    scalars
+        .iter()
+        .zip(points)
+        .fold(G::zero(), |sum, (scalar, point)| sum + point * *scalar)
+}
+
+fn inner_product<S: Scalar>(left: &[S], right: &[S]) -> S {
+    left.iter()
+        .zip(right.iter())
+        .fold(S::zero(), |sum, (l, r)| sum + *l * *r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptography::elgamal::Keypair;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar as RScalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn verifiable_shuffle() {
+        let mut rng = OsRng;
+        let keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        let commitment_key = VectorCommitmentKey::<RistrettoPoint>::generate(&[0u8], 7);
+
+        for size in [1usize, 2, 7].iter() {
+            let messages: Vec<RScalar> = (0..*size as u64).map(RScalar::from).collect();
+            let inputs: Vec<Ciphertext<RistrettoPoint>> = messages
+                .iter()
+                .map(|m| keypair.public_key.encrypt(m, &mut rng))
+                .collect();
+
+            let (outputs, proof) = shuffle(&keypair.public_key, &commitment_key, &inputs, &mut rng);
+            assert!(proof
+                .verify(&keypair.public_key, &commitment_key, &inputs, &outputs)
+                .is_ok());
+
+            // The outputs decrypt to a permutation of the messages.
+            let mut decrypted: Vec<RistrettoPoint> = outputs
+                .iter()
+                .map(|ciphertext| keypair.secret_key.decrypt_point(ciphertext))
+                .collect();
+            for m in messages.iter() {
+                let position = decrypted
+                    .iter()
+                    .position(|point| *point == RistrettoPoint::generator() * m)
+                    .expect("every message is in the output");
+                decrypted.swap_remove(position);
+            }
+
+            // The proof does not verify for different inputs, or a different key.
+            let other_key = VectorCommitmentKey::<RistrettoPoint>::generate(&[1u8], 7);
+            assert!(proof
+                .verify(&keypair.public_key, &other_key, &inputs, &outputs)
+                .is_err());
+            let short_key = VectorCommitmentKey::<RistrettoPoint>::generate(&[0u8], size - 1);
+            assert!(proof
+                .verify(&keypair.public_key, &short_key, &inputs, &outputs)
+                .is_err());
+            let mut other_inputs = inputs.clone();
+            other_inputs[0] = keypair.public_key.encrypt(&messages[0], &mut rng);
+            assert!(proof
+                .verify(
+                    &keypair.public_key,
+                    &commitment_key,
+                    &other_inputs,
+                    &outputs
+                )
+                .is_err());
+            assert!(proof
+                .verify(
+                    &keypair.public_key,
+                    &commitment_key,
+                    &inputs[1..],
+                    &outputs[1..]
+                )
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn invalid_shuffle() {
+        let mut rng = OsRng;
+        let keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        let commitment_key = VectorCommitmentKey::<RistrettoPoint>::generate(&[0u8], 7);
+        let inputs: Vec<Ciphertext<RistrettoPoint>> = (0..4u64)
+            .map(|m| keypair.public_key.encrypt(&RScalar::from(m), &mut rng))
+            .collect();
+        let permutation = [2usize, 0, 3, 1];
+        let randomness: Vec<RScalar> = (0..4).map(|_| RScalar::random(&mut rng)).collect();
+        let mut outputs: Vec<Ciphertext<RistrettoPoint>> = permutation
+            .iter()
+            .zip(randomness.iter())
+            .map(|(&j, r)| inputs[j].rerandomize_with_r(&keypair.public_key, r))
+            .collect();
+
+        let proof = Zkp::generate(
+            &keypair.public_key,
+            &commitment_key,
+            &inputs,
+            &outputs,
+            &permutation,
+            &randomness,
+            &mut rng,
+        );
+        assert!(proof
+            .verify(&keypair.public_key, &commitment_key, &inputs, &outputs)
+            .is_ok());
+
+        // Replacing one of the outputs by the encryption of a different message, or
+        // duplicating an output, invalidates the proof.
+        outputs[1] = outputs[1] + keypair.public_key.encrypt(&RScalar::one(), &mut rng);
+        let proof = Zkp::generate(
+            &keypair.public_key,
+            &commitment_key,
+            &inputs,
+            &outputs,
+            &permutation,
+            &randomness,
+            &mut rng,
+        );
+        assert!(proof
+            .verify(&keypair.public_key, &commitment_key, &inputs, &outputs)
+            .is_err());
+
+        outputs[1] = outputs[0];
+        assert!(proof
+            .verify(&keypair.public_key, &commitment_key, &inputs, &outputs)
+            .is_err());
+    }
+}
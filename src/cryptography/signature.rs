@@ -0,0 +1,75 @@
+//! Schnorr signatures over a `PrimeGroupElement`. A signature over `message` with secret key
+//! `sk`, and public key `pk = g^sk`, is computed by sampling a random nonce `k`, and computing
+//!
+//! `R = g^k`, `c = H(pk, R, message)` and `s = k + c * sk`.
+//!
+//! The signature consists of the pair `(c, s)`, and is verified by recomputing
+//! `R = g^s * pk^{-c}` and checking that `c = H(pk, R, message)`.
+use crate::cryptography::elgamal::{PublicKey, SecretKey};
+use crate::traits::{PrimeGroupElement, Scalar};
+use blake2::Blake2b;
+use rand_core::{CryptoRng, RngCore};
+
+/// Schnorr signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature<G: PrimeGroupElement> {
+    challenge: G::CorrespondingScalar,
+    response: G::CorrespondingScalar,
+}
+
+impl<G: PrimeGroupElement> Signature<G> {
+    // Compute the challenge binding the public key, the nonce commitment and the message.
+    fn challenge(pk: &G, nonce_commitment: &G, message: &[u8]) -> G::CorrespondingScalar {
+        let mut ctx: Vec<u8> = Vec::new();
+        ctx.extend_from_slice(&pk.to_bytes());
+        ctx.extend_from_slice(&nonce_commitment.to_bytes());
+        ctx.extend_from_slice(message);
+
+        G::CorrespondingScalar::hash_to_scalar::<Blake2b>(&ctx)
+    }
+
+    /// Sign `message` with the secret key `sk`.
+    pub fn sign<R>(sk: &SecretKey<G>, message: &[u8], rng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let pk = G::generator_mul(&sk.sk);
+        let k = G::CorrespondingScalar::random(rng);
+        let challenge = Self::challenge(&pk, &G::generator_mul(&k), message);
+        let response = k + challenge * sk.sk;
+
+        Signature {
+            challenge,
+            response,
+        }
+    }
+
+    /// Verify that `self` is a valid signature of `message` under public key `pk`.
+    pub fn verify(&self, pk: &PublicKey<G>, message: &[u8]) -> bool {
+        let nonce_commitment = G::generator() * self.response - pk.pk * self.challenge;
+        // no need for constant time equality because of the hash in challenge()
+        Self::challenge(&pk.pk, &nonce_commitment, message) == self.challenge
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptography::elgamal::Keypair;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use rand_core::OsRng;
+
+    #[test]
+    fn sign_and_verify() {
+        let mut rng = OsRng;
+        let keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        let other_keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+
+        let message = b"Message to be signed.";
+        let signature = Signature::sign(&keypair.secret_key, message, &mut rng);
+
+        assert!(signature.verify(&keypair.public_key, message));
+        assert!(!signature.verify(&keypair.public_key, b"Another message."));
+        assert!(!signature.verify(&other_keypair.public_key, message));
+    }
+}
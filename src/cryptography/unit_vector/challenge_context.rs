@@ -0,0 +1,59 @@
+use crate::cryptography::commitment::CommitmentKey;
+use crate::cryptography::elgamal::{Ciphertext, PublicKey};
+use crate::traits::{PrimeGroupElement, Scalar};
+use blake2::Blake2b;
+
+/// Challenge context for the unit vector proof. The common reference string are the public
+/// key of the encryption and the commitment key, and the statement is the vector of
+/// ciphertexts. The proof has two challenges, the first one computed after the commitments
+/// to the bits of the index are "sent", and the second one after the encryptions of the
+/// coefficients of the polynomials.
+#[derive(Debug, Clone)]
+pub struct ChallengeContext(Vec<u8>);
+
+impl ChallengeContext {
+    /// Initialise the challenge context, by including the common reference string and the full statement
+    pub(crate) fn new<G: PrimeGroupElement>(
+        public_key: &PublicKey<G>,
+        commitment_key: &CommitmentKey<G>,
+        ciphertexts: &[Ciphertext<G>],
+    ) -> Self {
+        let mut ctx: Vec<u8> = Vec::new();
+        ctx.extend_from_slice(&public_key.pk.to_bytes());
+        ctx.extend_from_slice(&commitment_key.h().to_bytes());
+        for ciphertext in ciphertexts {
+            ctx.extend_from_slice(&ciphertext.e1.to_bytes());
+            ctx.extend_from_slice(&ciphertext.e2.to_bytes());
+        }
+
+        ChallengeContext(ctx)
+    }
+
+    /// Generation of the `first_challenge`, after the commitments to the bits of the index.
+    pub(crate) fn first_challenge<G: PrimeGroupElement>(
+        &mut self,
+        bit_commitments: &[[G; 3]],
+    ) -> G::CorrespondingScalar {
+        for commitments in bit_commitments {
+            for commitment in commitments {
+                self.0.extend_from_slice(&commitment.to_bytes());
+            }
+        }
+
+        <G as PrimeGroupElement>::CorrespondingScalar::hash_to_scalar::<Blake2b>(&self.0)
+    }
+
+    /// Generation of the `second_challenge`, after the encryptions of the coefficients of
+    /// the polynomials.
+    pub(crate) fn second_challenge<G: PrimeGroupElement>(
+        &mut self,
+        coefficient_ciphertexts: &[Ciphertext<G>],
+    ) -> G::CorrespondingScalar {
+        for ciphertext in coefficient_ciphertexts {
+            self.0.extend_from_slice(&ciphertext.e1.to_bytes());
+            self.0.extend_from_slice(&ciphertext.e2.to_bytes());
+        }
+
+        <G as PrimeGroupElement>::CorrespondingScalar::hash_to_scalar::<Blake2b>(&self.0)
+    }
+}
@@ -0,0 +1,338 @@
+//! Non-interactive Zero Knowledge proof that a vector of ciphertexts encrypts a unit vector,
+//! i.e. a vector with a single one and zeros elsewhere, following the proof of
+//! [Bootle et al.](https://eprint.iacr.org/2015/643.pdf), as used in the unit vector proof
+//! of `chain-vote`.
+//!
+//! The proof is the following:
+//!
+//! `NIZK{(pk, ck, (C_0, ..., C_{n-1})), (i, (r_0, ..., r_{n-1})): C_j = Enc_pk(δ_{i,j}; r_j)}`
+//!
+//! where `δ_{i,j}` is one if `i = j` and zero otherwise. The vector is padded with
+//! encryptions of zero up to the next power of two, `N = 2^m`, and the prover commits with
+//! `ck` to the `m` bits of the index `i`, proving that each of them is either zero or one.
+//! For every position `j`, the product of the responses selected by the bits of `j`
+//! defines a polynomial `p_j(x)` of degree `m`, whose leading coefficient is one if `i = j`
+//! and zero otherwise. The prover encrypts the lower coefficients of the polynomials, such
+//! that the verifier checks homomorphically that the ciphertexts encrypt the leading
+//! coefficients, with a proof of logarithmic size in the length of the vector.
+use super::challenge_context::ChallengeContext;
+use crate::cryptography::commitment::CommitmentKey;
+use crate::cryptography::elgamal::{Ciphertext, PublicKey};
+use crate::errors::ProofError;
+use crate::polynomial::Polynomial;
+use crate::traits::{PrimeGroupElement, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+/// Proof that a vector of ciphertexts encrypts a unit vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zkp<G: PrimeGroupElement> {
+    // Commitments to each bit `i_l` of the index, `(I_l, B_l, A_l)`
+    bit_commitments: Vec<[G; 3]>,
+    // Encryptions of the lower coefficients of the polynomials
+    coefficient_ciphertexts: Vec<Ciphertext<G>>,
+    // Responses of each bit of the index, `(z_l, w_l, v_l)`
+    bit_responses: Vec<[G::CorrespondingScalar; 3]>,
+    // Randomness of the homomorphic combination of the ciphertexts
+    randomness_response: G::CorrespondingScalar,
+}
+
+impl<G: PrimeGroupElement> Zkp<G> {
+    /// Generate a proof that `ciphertexts` encrypt the unit vector with a one at position
+    /// `unit_vector_index`, under `public_key`, where `randomness[j]` is the randomness used
+    /// to encrypt `ciphertexts[j]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `unit_vector_index` is not smaller than the number of ciphertexts, or if
+    /// there are not as many randomness values as ciphertexts.
+    pub fn generate<R>(
+        public_key: &PublicKey<G>,
+        commitment_key: &CommitmentKey<G>,
+        ciphertexts: &[Ciphertext<G>],
+        randomness: &[G::CorrespondingScalar],
+        unit_vector_index: usize,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        assert!(unit_vector_index < ciphertexts.len());
+        assert_eq!(ciphertexts.len(), randomness.len());
+        let nr_bits = nr_bits(ciphertexts.len());
+
+        let index_bits: Vec<G::CorrespondingScalar> = (0..nr_bits)
+            .map(|l| {
+                if (unit_vector_index >> l) & 1 == 1 {
+                    G::CorrespondingScalar::one()
+                } else {
+                    G::CorrespondingScalar::zero()
+                }
+            })
+            .collect();
+        // Blinding values `(alpha_l, beta_l, gamma_l, delta_l)` of each bit
+        let blinders: Vec<[G::CorrespondingScalar; 4]> = (0..nr_bits)
+            .map(|_| {
+                [
+                    G::CorrespondingScalar::random(rng),
+                    G::CorrespondingScalar::random(rng),
+                    G::CorrespondingScalar::random(rng),
+                    G::CorrespondingScalar::random(rng),
+                ]
+            })
+            .collect();
+        let bit_commitments: Vec<[G; 3]> = index_bits
+            .iter()
+            .zip(blinders.iter())
+            .map(|(bit, [alpha, beta, gamma, delta])| {
+                [
+                    commitment_key.commit_with_random(bit, alpha),
+                    commitment_key.commit_with_random(beta, gamma),
+                    commitment_key.commit_with_random(&(*bit * *beta), delta),
+                ]
+            })
+            .collect();
+
+        let mut challenge_context = ChallengeContext::new(public_key, commitment_key, ciphertexts);
+        let batch_challenge = challenge_context.first_challenge(&bit_commitments);
+
+        // Lower coefficients of the polynomials, `sum_j y^j p_j(x)`, batched with the
+        // first challenge `y`
+        let mut coefficients = vec![G::CorrespondingScalar::zero(); nr_bits];
+        for (j, batch_power) in (0..1 << nr_bits).zip(batch_challenge.exp_iter()) {
+            let polynomial = index_bits.iter().zip(blinders.iter()).enumerate().fold(
+                Polynomial::from_coefficients(&[G::CorrespondingScalar::one()]),
+                |polynomial, (l, (&bit, [_, beta, _, _]))| {
+                    let factor = if (j >> l) & 1 == 1 {
+                        Polynomial::from_coefficients(&[*beta, bit])
+                    } else {
+                        Polynomial::from_coefficients(&[
+                            -*beta,
+                            G::CorrespondingScalar::one() - bit,
+                        ])
+                    };
+                    polynomial * factor
+                },
+            );
+            for (k, coefficient) in coefficients.iter_mut().enumerate() {
+                *coefficient += batch_power * *polynomial.get_coefficient_at(k);
+            }
+        }
+        let coefficient_randomness: Vec<G::CorrespondingScalar> = (0..nr_bits)
+            .map(|_| G::CorrespondingScalar::random(rng))
+            .collect();
+        let coefficient_ciphertexts: Vec<Ciphertext<G>> = coefficients
+            .iter()
+            .zip(coefficient_randomness.iter())
+            .map(|(coefficient, r)| public_key.encrypt_with_r(coefficient, r))
+            .collect();
+
+        let challenge = challenge_context.second_challenge(&coefficient_ciphertexts);
+
+        let bit_responses = index_bits
+            .iter()
+            .zip(blinders.iter())
+            .map(|(&bit, &[alpha, beta, gamma, delta])| {
+                let z = bit * challenge + beta;
+                [
+                    z,
+                    alpha * challenge + gamma,
+                    alpha * (challenge - z) + delta,
+                ]
+            })
+            .collect();
+
+        // x^m * sum_j y^j r_j + sum_k x^k R_k, where padded ciphertexts have no randomness
+        let mut challenge_powers = challenge.exp_iter();
+        let mut randomness_response = challenge_powers
+            .by_ref()
+            .take(nr_bits)
+            .zip(coefficient_randomness.iter())
+            .fold(G::CorrespondingScalar::zero(), |sum, (power, r)| {
+                sum + power * *r
+            });
+        let leading_power = challenge_powers.next().expect("infinite iterator");
+        randomness_response += randomness
+            .iter()
+            .zip(batch_challenge.exp_iter())
+            .fold(G::CorrespondingScalar::zero(), |sum, (r, power)| {
+                sum + power * *r
+            })
+            * leading_power;
+
+        Zkp {
+            bit_commitments,
+            coefficient_ciphertexts,
+            bit_responses,
+            randomness_response,
+        }
+    }
+
+    /// Verify that `ciphertexts` encrypt a unit vector under `public_key`.
+    pub fn verify(
+        &self,
+        public_key: &PublicKey<G>,
+        commitment_key: &CommitmentKey<G>,
+        ciphertexts: &[Ciphertext<G>],
+    ) -> Result<(), ProofError> {
+        if ciphertexts.is_empty() {
+            return Err(ProofError::ZkpVerificationFailed);
+        }
+        let nr_bits = nr_bits(ciphertexts.len());
+        if self.bit_commitments.len() != nr_bits
+            || self.bit_responses.len() != nr_bits
+            || self.coefficient_ciphertexts.len() != nr_bits
+        {
+            return Err(ProofError::ZkpVerificationFailed);
+        }
+
+        let mut challenge_context = ChallengeContext::new(public_key, commitment_key, ciphertexts);
+        let batch_challenge = challenge_context.first_challenge(&self.bit_commitments);
+        let challenge = challenge_context.second_challenge(&self.coefficient_ciphertexts);
+
+        // Every bit of the index is either zero or one
+        let valid_bits = self
+            .bit_commitments
+            .iter()
+            .zip(self.bit_responses.iter())
+            .all(|(&[i, b, a], &[z, w, v])| {
+                i * challenge + b == commitment_key.commit_with_random(&z, &w)
+                    && i * (challenge - z) + a
+                        == commitment_key.commit_with_random(&G::CorrespondingScalar::zero(), &v)
+            });
+        if !valid_bits {
+            return Err(ProofError::ZkpVerificationFailed);
+        }
+
+        // sum_j y^j p_j(x), with p_j(x) the product of z_l, if bit l of j is one, or
+        // x - z_l otherwise
+        let batched_evaluation = (0..1 << nr_bits).zip(batch_challenge.exp_iter()).fold(
+            G::CorrespondingScalar::zero(),
+            |sum, (j, batch_power)| {
+                let evaluation = self.bit_responses.iter().enumerate().fold(
+                    G::CorrespondingScalar::one(),
+                    |product, (l, &[z, _, _])| {
+                        if (j >> l) & 1 == 1 {
+                            product * z
+                        } else {
+                            product * (challenge - z)
+                        }
+                    },
+                );
+                sum + batch_power * evaluation
+            },
+        );
+
+        // x^m * sum_j y^j C_j + sum_k x^k D_k
+        let mut challenge_powers = challenge.exp_iter();
+        let mut scalars: Vec<G::CorrespondingScalar> =
+            challenge_powers.by_ref().take(nr_bits).collect();
+        let leading_power = challenge_powers.next().expect("infinite iterator");
+        scalars.extend(
+            batch_challenge
+                .exp_iter()
+                .take(ciphertexts.len())
+                .map(|power| power * leading_power),
+        );
+        let all_ciphertexts = self
+            .coefficient_ciphertexts
+            .iter()
+            .chain(ciphertexts.iter());
+        let e1 = G::vartime_multiscalar_multiplication(
+            scalars.iter().copied(),
+            all_ciphertexts.clone().map(|ciphertext| ciphertext.e1),
+        );
+        let e2 = G::vartime_multiscalar_multiplication(
+            scalars.iter().copied(),
+            all_ciphertexts.map(|ciphertext| ciphertext.e2),
+        );
+
+        let combined = Ciphertext { e1, e2 };
+        if combined == public_key.encrypt_with_r(&batched_evaluation, &self.randomness_response) {
+            Ok(())
+        } else {
+            Err(ProofError::ZkpVerificationFailed)
+        }
+    }
+}
+
+// Number of bits of the indices of a vector of size `size`, with at least one bit.
+fn nr_bits(size: usize) -> usize {
+    let bits = usize::BITS - size.saturating_sub(1).leading_zeros();
+    bits.max(1) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptography::elgamal::Keypair;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar as RScalar;
+    use rand_core::OsRng;
+
+    fn encrypt(
+        public_key: &PublicKey<RistrettoPoint>,
+        messages: &[u64],
+    ) -> (Vec<Ciphertext<RistrettoPoint>>, Vec<RScalar>) {
+        messages
+            .iter()
+            .map(|&message| public_key.encrypt_return_r(&RScalar::from(message), &mut OsRng))
+            .unzip()
+    }
+
+    #[test]
+    fn unit_vector_proof() {
+        let mut rng = OsRng;
+        let keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        let commitment_key = CommitmentKey::<RistrettoPoint>::generate(&[0u8]);
+
+        for size in 1..=5 {
+            for index in 0..size {
+                let messages: Vec<u64> = (0..size).map(|j| (j == index) as u64).collect();
+                let (ciphertexts, randomness) = encrypt(&keypair.public_key, &messages);
+                let proof = Zkp::generate(
+                    &keypair.public_key,
+                    &commitment_key,
+                    &ciphertexts,
+                    &randomness,
+                    index,
+                    &mut rng,
+                );
+                assert!(proof
+                    .verify(&keypair.public_key, &commitment_key, &ciphertexts)
+                    .is_ok());
+
+                // The proof does not verify for other ciphertexts or keys
+                let (other_ciphertexts, _) = encrypt(&keypair.public_key, &messages);
+                assert!(proof
+                    .verify(&keypair.public_key, &commitment_key, &other_ciphertexts)
+                    .is_err());
+                let other_key = CommitmentKey::<RistrettoPoint>::generate(&[1u8]);
+                assert!(proof
+                    .verify(&keypair.public_key, &other_key, &ciphertexts)
+                    .is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn non_unit_vector() {
+        let mut rng = OsRng;
+        let keypair = Keypair::<RistrettoPoint>::generate(&mut rng);
+        let commitment_key = CommitmentKey::<RistrettoPoint>::generate(&[0u8]);
+
+        for messages in [[0u64, 0, 0], [1, 1, 0], [0, 2, 0], [0, 0, 0]].iter() {
+            let (ciphertexts, randomness) = encrypt(&keypair.public_key, messages);
+            let proof = Zkp::generate(
+                &keypair.public_key,
+                &commitment_key,
+                &ciphertexts,
+                &randomness,
+                1,
+                &mut rng,
+            );
+            assert!(proof
+                .verify(&keypair.public_key, &commitment_key, &ciphertexts)
+                .is_err());
+        }
+    }
+}
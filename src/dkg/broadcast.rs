@@ -2,11 +2,15 @@
 use crate::cryptography::elgamal::SymmetricKey;
 use crate::cryptography::{
     correct_hybrid_decryption_key::CorrectHybridDecrKeyZkp, elgamal::HybridCiphertext,
+    schnorr::SchnorrZkp,
 };
 use crate::dkg::committee::Environment;
+use crate::dkg::equivocation::SignedDigest;
 use crate::dkg::procedure_keys::{MemberCommunicationKey, MemberCommunicationPublicKey};
-use crate::errors::DkgError;
+use crate::errors::{DkgError, ProofError};
 use crate::traits::{PrimeGroupElement, Scalar};
+use crate::vss::pedersen;
+use blake2::{Blake2b, Digest};
 use rand_core::{CryptoRng, RngCore};
 
 /// Struct that contains the index of the receiver, and its two encrypted
@@ -22,13 +26,131 @@ pub struct EncryptedShares<G: PrimeGroupElement> {
 /// Struct that contains two decrypted shares, together with the blinding commitment
 /// of the coefficients of the associated polynomial.
 /// todo: ok not linking an index to the share? I think its fine, as this is handled locally
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone)]
 pub struct DecryptedShares<G: PrimeGroupElement> {
     pub decrypted_share: <G as PrimeGroupElement>::CorrespondingScalar,
     pub decrypted_randomness: <G as PrimeGroupElement>::CorrespondingScalar,
     pub committed_coefficients: Vec<G>,
 }
 
+impl<G: PrimeGroupElement> std::fmt::Debug for DecryptedShares<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecryptedShares")
+            .field("committed_coefficients", &self.committed_coefficients)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: PrimeGroupElement> PartialEq for DecryptedShares<G> {
+    fn eq(&self, other: &Self) -> bool {
+        // Evaluate both secret comparisons, to not leak which one differs.
+        let equal_share = self
+            .decrypted_share
+            .constant_time_eq(&other.decrypted_share);
+        let equal_randomness = self
+            .decrypted_randomness
+            .constant_time_eq(&other.decrypted_randomness);
+        equal_share & equal_randomness
+            && self.committed_coefficients == other.committed_coefficients
+    }
+}
+
+impl<G: PrimeGroupElement> Eq for DecryptedShares<G> {}
+
+impl<G: PrimeGroupElement> DecryptedShares<G> {
+    /// Debug representation of the shares which, unlike `Debug`, includes the decrypted
+    /// values. Handle the output with care, as it discloses the shares.
+    pub fn danger_debug(&self) -> String {
+        format!(
+            "DecryptedShares {{ decrypted_share: {:?}, decrypted_randomness: {:?}, committed_coefficients: {:?} }}",
+            self.decrypted_share, self.decrypted_randomness, self.committed_coefficients
+        )
+    }
+
+    /// Verify that the shares received by member `recipient_index` are consistent with the
+    /// committed coefficients of the dealer, i.e.
+    /// \\( g^{s}h^{s'} = \prod_{l=0}^t E_{l}^{i^l}. \\)
+    pub fn verify(&self, environment: &Environment<G>, recipient_index: usize) -> bool {
+        let share = pedersen::Share {
+            index: recipient_index,
+            value: self.decrypted_share,
+            randomness: self.decrypted_randomness,
+        };
+        pedersen::verify_share(
+            &environment.commitment_key,
+            environment.threshold,
+            &self.committed_coefficients,
+            &share,
+        )
+    }
+
+    /// Verify the shares received by member `recipient_index` from several dealers with a
+    /// single multiscalar multiplication. The verification equations of every dealer are
+    /// combined with random weights \\( \rho_j \\), and the function checks that
+    /// \\( g^{\sum_j \rho_j s_j}h^{\sum_j \rho_j s_j'} = \prod_j\prod_{l=0}^t E_{j,l}^{\rho_j i^l}. \\)
+    ///
+    /// If the check fails, at least one of the dealers misbehaved, and the shares need to be
+    /// verified individually with `verify` to identify the culprits. The check also fails if
+    /// any dealer did not commit to exactly `threshold + 1` coefficients.
+    pub fn batch_verify<R>(
+        environment: &Environment<G>,
+        recipient_index: usize,
+        shares: &[&Self],
+        rng: &mut R,
+    ) -> bool
+    where
+        R: CryptoRng + RngCore,
+    {
+        if shares
+            .iter()
+            .any(|share| share.committed_coefficients.len() != environment.threshold + 1)
+        {
+            return false;
+        }
+        let index_pow: Vec<G::CorrespondingScalar> =
+            <G::CorrespondingScalar as Scalar>::from_u64(recipient_index as u64)
+                .exp_iter()
+                .take(environment.threshold + 1)
+                .collect();
+
+        let mut combined_share = G::CorrespondingScalar::zero();
+        let mut combined_randomness = G::CorrespondingScalar::zero();
+        let mut scalars = Vec::with_capacity(shares.len() * (environment.threshold + 1) + 2);
+        let mut points = Vec::with_capacity(shares.len() * (environment.threshold + 1) + 2);
+
+        for share in shares {
+            let weight = G::CorrespondingScalar::random(rng);
+            combined_share += weight * share.decrypted_share;
+            combined_randomness += weight * share.decrypted_randomness;
+
+            scalars.extend(index_pow.iter().map(|&pow| -(weight * pow)));
+            points.extend(share.committed_coefficients.iter().copied());
+        }
+
+        scalars.push(combined_share);
+        points.push(G::generator());
+        scalars.push(combined_randomness);
+        points.push(environment.commitment_key.h());
+
+        G::vartime_multiscalar_multiplication(scalars, points) == G::zero()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<G: PrimeGroupElement> zeroize::Zeroize for DecryptedShares<G> {
+    fn zeroize(&mut self) {
+        self.decrypted_share.erase();
+        self.decrypted_randomness.erase();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<G: PrimeGroupElement> Drop for DecryptedShares<G> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
 /// Struct that contains misbehaving parties detected in round 1. These
 /// consist of the misbehaving member's index, the error which failed,
 /// and a `ProofOfMisbehaviour`, which contains the invalid encrypted shares
@@ -84,7 +206,9 @@ impl<G: PrimeGroupElement> MisbehavingPartiesRound1<G> {
             .exp_iter()
             .take(environment.threshold + 1);
 
-        let check_element = environment.commitment_key.h * randomness + G::generator() * share;
+        let check_element = environment
+            .commitment_key
+            .commit_with_random(&share, &randomness);
         let multi_scalar = G::vartime_multiscalar_multiplication(
             index_pow,
             accused_broadcast.committed_coefficients.clone(),
@@ -119,15 +243,16 @@ impl<G: PrimeGroupElement> MisbehavingPartiesRound3<G> {
             .exp_iter()
             .take(environment.threshold + 1);
 
-        let failing_check = G::generator() * self.decrypted_share;
+        let failing_check = G::generator_mul(&self.decrypted_share);
         let failing_multi_scalar =
             G::vartime_multiscalar_multiplication(index_pow, committed_coefficients.clone());
 
         let index_pow = <G::CorrespondingScalar as Scalar>::from_u64(accuser_index as u64)
             .exp_iter()
             .take(environment.threshold + 1);
-        let passing_check = G::generator() * self.decrypted_share
-            + environment.commitment_key.h * self.decrypted_randomness;
+        let passing_check = environment
+            .commitment_key
+            .commit_with_random(&self.decrypted_share, &self.decrypted_randomness);
         let passing_multi_scalar = G::vartime_multiscalar_multiplication(
             index_pow,
             randomised_committed_coefficients.clone(),
@@ -151,32 +276,328 @@ pub type MisbehavingPartiesRound4<G> = <G as PrimeGroupElement>::CorrespondingSc
 /// randomised commitments of the parties VSS polynomials. `encrypted_shares` is a vector
 /// of size `n` (where `n` is the number of parties), (todo: we want to check this as well somewhere)
 /// where `encrypted_share[i]` represents the encrypted shares of party `i`.  
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BroadcastPhase1<G: PrimeGroupElement> {
     pub committed_coefficients: Vec<G>,
     pub encrypted_shares: Vec<EncryptedShares<G>>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BroadcastPhase2<G: PrimeGroupElement> {
     pub misbehaving_parties: Vec<MisbehavingPartiesRound1<G>>,
 }
 
-#[derive(Clone)]
+/// Structure representing the broadcast messages of `Phase3`. The `committed_coefficients` are
+/// the commitments \\( A_{i,l} = g^{a_{i,l}} \\) of the dealer's sharing polynomial, and
+/// `proof_of_knowledge` proves knowledge of the secret \\( a_{i,0} \\) behind
+/// \\( A_{i,0} \\), which prevents a dealer from choosing its constant term as a function of
+/// the others' (rogue-key attacks).
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BroadcastPhase3<G: PrimeGroupElement> {
     pub committed_coefficients: Vec<G>,
+    pub proof_of_knowledge: SchnorrZkp<G>,
 }
 
-#[derive(Clone)]
+impl<G: PrimeGroupElement> BroadcastPhase3<G> {
+    /// Verify the proof of knowledge of the constant term of the broadcast of member
+    /// `sender_index`, in the session defined by `environment`.
+    pub fn verify_proof_of_knowledge(
+        &self,
+        environment: &Environment<G>,
+        sender_index: usize,
+    ) -> Result<(), ProofError> {
+        verify_constant_term_proof(
+            environment,
+            sender_index,
+            &self.committed_coefficients,
+            &self.proof_of_knowledge,
+        )
+    }
+}
+
+// Context of the proof of knowledge of the constant term, which binds it to the session and to
+// the dealer, so that a member cannot replay the proof of another.
+fn constant_term_context<G: PrimeGroupElement>(
+    environment: &Environment<G>,
+    sender_index: usize,
+) -> Vec<u8> {
+    let mut bytes = b"DKG constant term".to_vec();
+    bytes.extend_from_slice(&(environment.session_id.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&environment.session_id);
+    bytes.extend_from_slice(&(sender_index as u64).to_le_bytes());
+    bytes
+}
+
+/// Generate the proof of knowledge of the constant term `secret` of the sharing polynomial of
+/// member `sender_index`, whose commitment is `g^secret`.
+pub(crate) fn prove_constant_term<G, R>(
+    environment: &Environment<G>,
+    sender_index: usize,
+    secret: &G::CorrespondingScalar,
+    rng: &mut R,
+) -> SchnorrZkp<G>
+where
+    G: PrimeGroupElement,
+    R: CryptoRng + RngCore,
+{
+    SchnorrZkp::generate(
+        &G::generator(),
+        &G::generator_mul(secret),
+        secret,
+        &constant_term_context(environment, sender_index),
+        rng,
+    )
+}
+
+/// Verify the proof of knowledge of the constant term of `committed_coefficients`, broadcast
+/// by member `sender_index`.
+pub(crate) fn verify_constant_term_proof<G: PrimeGroupElement>(
+    environment: &Environment<G>,
+    sender_index: usize,
+    committed_coefficients: &[G],
+    proof: &SchnorrZkp<G>,
+) -> Result<(), ProofError> {
+    let constant_term = committed_coefficients
+        .first()
+        .ok_or(ProofError::ZkpVerificationFailed)?;
+    proof.verify(
+        &G::generator(),
+        constant_term,
+        &constant_term_context(environment, sender_index),
+    )
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BroadcastPhase4<G: PrimeGroupElement> {
     pub misbehaving_parties: Vec<MisbehavingPartiesRound3<G>>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BroadcastPhase5<G: PrimeGroupElement> {
     pub misbehaving_parties: Vec<Option<MisbehavingPartiesRound4<G>>>,
 }
 
+/// Canonical encoding of the broadcast messages of the different phases. The encoding is used
+/// to compute a digest of each member's view of a phase, so that members can compare what they
+/// received from every sender.
+pub trait PhaseBroadcast {
+    /// Phase in which the message is broadcast.
+    const PHASE: usize;
+
+    /// Canonical byte encoding of the message.
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Digest of the message, domain separated by the phase.
+    fn digest(&self) -> [u8; 64] {
+        let hash = Blake2b::new()
+            .chain((Self::PHASE as u64).to_le_bytes())
+            .chain(self.to_bytes())
+            .finalize();
+        let mut digest = [0u8; 64];
+        digest.copy_from_slice(&hash);
+        digest
+    }
+}
+
+fn encode_length(bytes: &mut Vec<u8>, length: usize) {
+    bytes.extend_from_slice(&(length as u64).to_le_bytes());
+}
+
+fn encode_points<G: PrimeGroupElement>(bytes: &mut Vec<u8>, points: &[G]) {
+    encode_length(bytes, points.len());
+    for point in points {
+        bytes.extend_from_slice(&point.to_bytes());
+    }
+}
+
+fn encode_hybrid_ciphertext<G: PrimeGroupElement>(
+    bytes: &mut Vec<u8>,
+    ciphertext: &HybridCiphertext<G>,
+) {
+    bytes.extend_from_slice(&ciphertext.e1.to_bytes());
+    encode_length(bytes, ciphertext.e2.len());
+    bytes.extend_from_slice(&ciphertext.e2);
+}
+
+// Only share validity failures and out of bounds scalars are valid accusations of round 1.
+fn encode_accusation_error(error: &DkgError) -> u8 {
+    match error {
+        DkgError::ScalarOutOfBounds => 0,
+        DkgError::ShareValidityFailed => 1,
+        _ => u8::MAX,
+    }
+}
+
+impl<G: PrimeGroupElement> PhaseBroadcast for BroadcastPhase1<G> {
+    const PHASE: usize = 1;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        encode_points(&mut bytes, &self.committed_coefficients);
+        encode_length(&mut bytes, self.encrypted_shares.len());
+        for shares in &self.encrypted_shares {
+            encode_length(&mut bytes, shares.recipient_index);
+            encode_hybrid_ciphertext(&mut bytes, &shares.encrypted_share);
+            encode_hybrid_ciphertext(&mut bytes, &shares.encrypted_randomness);
+        }
+        bytes
+    }
+}
+
+impl<G: PrimeGroupElement> PhaseBroadcast for BroadcastPhase2<G> {
+    const PHASE: usize = 2;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        encode_length(&mut bytes, self.misbehaving_parties.len());
+        for party in &self.misbehaving_parties {
+            encode_length(&mut bytes, party.accused_index);
+            bytes.push(encode_accusation_error(&party.accusation_error));
+            let proof = &party.proof_accusation;
+            bytes.extend_from_slice(&proof.share_key.group_repr.to_bytes());
+            bytes.extend_from_slice(&proof.randomness_key.group_repr.to_bytes());
+            bytes.extend_from_slice(&proof.proof_decryption_1.to_bytes());
+            bytes.extend_from_slice(&proof.proof_decryption_2.to_bytes());
+        }
+        bytes
+    }
+}
+
+impl<G: PrimeGroupElement> PhaseBroadcast for BroadcastPhase3<G> {
+    const PHASE: usize = 3;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        encode_points(&mut bytes, &self.committed_coefficients);
+        bytes.extend_from_slice(&self.proof_of_knowledge.to_bytes());
+        bytes
+    }
+}
+
+impl<G: PrimeGroupElement> PhaseBroadcast for BroadcastPhase4<G> {
+    const PHASE: usize = 4;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        encode_length(&mut bytes, self.misbehaving_parties.len());
+        for party in &self.misbehaving_parties {
+            encode_length(&mut bytes, party.accused_index);
+            bytes.extend_from_slice(&party.decrypted_share.to_bytes());
+            bytes.extend_from_slice(&party.decrypted_randomness.to_bytes());
+        }
+        bytes
+    }
+}
+
+impl<G: PrimeGroupElement> PhaseBroadcast for BroadcastPhase5<G> {
+    const PHASE: usize = 5;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        encode_length(&mut bytes, self.misbehaving_parties.len());
+        for share in &self.misbehaving_parties {
+            match share {
+                Some(share) => {
+                    bytes.push(1);
+                    bytes.extend_from_slice(&share.to_bytes());
+                }
+                None => bytes.push(0),
+            }
+        }
+        bytes
+    }
+}
+
+/// Broadcast message of phase `T::PHASE`, signed by its sender with its communication key.
+/// The signature covers the digest of the canonical encoding of `message`, the phase, the
+/// index of the sender, and the session identifier of the `Environment`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedBroadcast<G: PrimeGroupElement, T> {
+    pub message: T,
+    signed_digest: SignedDigest<G>,
+}
+
+impl<G: PrimeGroupElement, T: PhaseBroadcast> SignedBroadcast<G, T> {
+    /// Sign `message`, broadcast by the member with index `sender_index`.
+    pub fn sign<R>(
+        environment: &Environment<G>,
+        sender_index: usize,
+        message: T,
+        secret_key: &MemberCommunicationKey<G>,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let signed_digest =
+            SignedDigest::sign(environment, sender_index, &message, secret_key, rng);
+        Self {
+            message,
+            signed_digest,
+        }
+    }
+
+    /// Index of the member that signed the broadcast.
+    pub fn sender_index(&self) -> usize {
+        self.signed_digest.sender_index()
+    }
+
+    /// Signed digest of the message, which is echoed to detect equivocation.
+    pub fn signed_digest(&self) -> &SignedDigest<G> {
+        &self.signed_digest
+    }
+
+    /// Verify that the broadcast was signed by the member with communication public key
+    /// `sender_pk`, in the session defined by `environment`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidSignature` if the signature does not validate, or if it does not
+    /// correspond to `message`.
+    pub fn verify(
+        &self,
+        environment: &Environment<G>,
+        sender_pk: &MemberCommunicationPublicKey<G>,
+    ) -> Result<(), DkgError> {
+        if !self.signed_digest.matches(&self.message) {
+            return Err(DkgError::InvalidSignature);
+        }
+        self.signed_digest.verify(environment, sender_pk)
+    }
+}
+
+/// Given the signed broadcast messages of all other participants, ordered by sender index,
+/// returns the messages whose signature validates under the key of the expected sender in
+/// `members_pks`. Messages with an invalid signature, or signed by another member, are replaced
+/// by `None`, as if the sender did not broadcast.
+pub(crate) fn verified_messages<G: PrimeGroupElement, T: PhaseBroadcast + Clone>(
+    environment: &Environment<G>,
+    recipient_index: usize,
+    members_pks: &[MemberCommunicationPublicKey<G>],
+    broadcast_messages: &[Option<SignedBroadcast<G, T>>],
+) -> Vec<Option<T>> {
+    assert!(recipient_index > 0 && recipient_index <= environment.nr_members);
+    assert_eq!(members_pks.len(), environment.nr_members);
+    assert_eq!(broadcast_messages.len(), environment.nr_members - 1);
+
+    let mut broadcaster_indices: Vec<usize> = (1..(environment.nr_members + 1)).collect();
+    broadcaster_indices.remove(recipient_index - 1);
+
+    broadcaster_indices
+        .iter()
+        .zip(broadcast_messages.iter())
+        .map(|(&index, message)| match message {
+            Some(signed)
+                if signed.sender_index() == index
+                    && signed.verify(environment, &members_pks[index - 1]).is_ok() =>
+            {
+                Some(signed.message.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProofOfMisbehaviour<G: PrimeGroupElement> {
     share_key: SymmetricKey<G>,
@@ -271,7 +692,7 @@ impl<G: PrimeGroupElement> ProofOfMisbehaviour<G> {
             .take(environment.threshold + 1);
 
         let check_element =
-            environment.commitment_key.h * plaintext_1 + G::generator() * plaintext_2;
+            environment.commitment_key.h_mul(&plaintext_1) + G::generator_mul(&plaintext_2);
         let multi_scalar = G::vartime_multiscalar_multiplication(index_pow, committed_coeffs);
 
         if check_element != multi_scalar {
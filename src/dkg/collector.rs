@@ -0,0 +1,385 @@
+//! Order independent ingestion of broadcast messages. The `from_broadcast` functions of the
+//! different fetched states expect a slice with the messages of all other participants, ordered
+//! by sender index. In practice, messages are fetched from the broadcast channel in arbitrary
+//! order, might be received more than once, and a dishonest member might publish two different
+//! messages in the same phase. A `BroadcastCollector` receives `(sender, message)` pairs in any
+//! order, records each sender's message in its slot, and produces the fetched state of the
+//! phase once the phase deadline has passed.
+//!
+//! When messages are signed (see `SignedBroadcast`), the collector additionally verifies the
+//! signatures, keeps the signed digests to build the `EchoBroadcast` of the phase, and
+//! produces an `EquivocationProof` for every sender that signed two different messages.
+use crate::dkg::broadcast::{
+    BroadcastPhase1, BroadcastPhase2, BroadcastPhase3, BroadcastPhase4, BroadcastPhase5,
+    PhaseBroadcast, SignedBroadcast,
+};
+use crate::dkg::committee::{
+    Environment, MembersFetchedState1, MembersFetchedState2, MembersFetchedState3,
+    MembersFetchedState4, MembersFetchedState5,
+};
+use crate::dkg::equivocation::{EchoBroadcast, EquivocationProof, SignedDigest};
+use crate::dkg::procedure_keys::MemberCommunicationPublicKey;
+use crate::errors::DkgError;
+use crate::traits::PrimeGroupElement;
+
+/// Collector of the broadcast messages of a single phase, as seen by the member with index
+/// `recipient_index`. A member which broadcasts two different messages in the same phase is
+/// an equivocator, and is treated as if it did not broadcast at all.
+#[derive(Clone, Debug)]
+pub struct BroadcastCollector<G: PrimeGroupElement, T> {
+    environment: Environment<G>,
+    recipient_index: usize,
+    messages: Vec<Option<T>>,
+    equivocators: Vec<usize>,
+    signed_digests: Vec<Option<SignedDigest<G>>>,
+    equivocation_proofs: Vec<EquivocationProof<G>>,
+}
+
+/// Collector of the broadcast messages of phase 1.
+pub type BroadcastCollector1<G> = BroadcastCollector<G, BroadcastPhase1<G>>;
+/// Collector of the broadcast messages of phase 2.
+pub type BroadcastCollector2<G> = BroadcastCollector<G, BroadcastPhase2<G>>;
+/// Collector of the broadcast messages of phase 3.
+pub type BroadcastCollector3<G> = BroadcastCollector<G, BroadcastPhase3<G>>;
+/// Collector of the broadcast messages of phase 4.
+pub type BroadcastCollector4<G> = BroadcastCollector<G, BroadcastPhase4<G>>;
+/// Collector of the broadcast messages of phase 5.
+pub type BroadcastCollector5<G> = BroadcastCollector<G, BroadcastPhase5<G>>;
+
+impl<G: PrimeGroupElement, T: Clone + PartialEq> BroadcastCollector<G, T> {
+    /// Initialise an empty collector for the member with index `recipient_index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `recipient_index` is not in `1..=nr_members`.
+    pub fn new(environment: &Environment<G>, recipient_index: usize) -> Self {
+        assert!(recipient_index > 0 && recipient_index <= environment.nr_members);
+
+        Self {
+            environment: environment.clone(),
+            recipient_index,
+            messages: vec![None; environment.nr_members],
+            equivocators: Vec::new(),
+            signed_digests: vec![None; environment.nr_members],
+            equivocation_proofs: Vec::new(),
+        }
+    }
+
+    /// Record the `message` broadcast by the member with index `sender_index`. Messages can be
+    /// inserted in any order, and the recipient's own message can be inserted as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidSenderIndex` if `sender_index` does not belong to the committee, and
+    /// `DuplicateBroadcast` if the same message was already recorded for `sender_index`, in
+    /// which case the collector is left unchanged. If `sender_index` already broadcast a
+    /// different message, the sender is marked as an equivocator, its message is discarded,
+    /// and `EquivocatingBroadcast` is returned.
+    pub fn insert(&mut self, sender_index: usize, message: T) -> Result<(), DkgError> {
+        if sender_index == 0 || sender_index > self.environment.nr_members {
+            return Err(DkgError::InvalidSenderIndex(sender_index));
+        }
+
+        if self.equivocators.contains(&sender_index) {
+            return Err(DkgError::EquivocatingBroadcast(sender_index));
+        }
+
+        match &self.messages[sender_index - 1] {
+            Some(recorded) if recorded == &message => {
+                Err(DkgError::DuplicateBroadcast(sender_index))
+            }
+            Some(_) => {
+                self.messages[sender_index - 1] = None;
+                self.equivocators.push(sender_index);
+                Err(DkgError::EquivocatingBroadcast(sender_index))
+            }
+            None => {
+                self.messages[sender_index - 1] = Some(message);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the indices of the members that broadcast conflicting messages.
+    pub fn equivocators(&self) -> &[usize] {
+        &self.equivocators
+    }
+
+    /// Returns the broadcast messages of all members (including the recipient), ordered by
+    /// sender index. Members that did not broadcast, or that equivocated, are set to `None`.
+    pub fn broadcasts(&self) -> Vec<Option<T>> {
+        self.messages.clone()
+    }
+
+    /// Returns the broadcast messages of all members but the recipient, ordered by sender
+    /// index. This is the input expected by the `from_broadcast` functions.
+    pub fn others(&self) -> Vec<Option<T>> {
+        self.messages
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i + 1 != self.recipient_index)
+            .map(|(_, message)| message.clone())
+            .collect()
+    }
+}
+
+impl<G: PrimeGroupElement, T: PhaseBroadcast + Clone + PartialEq> BroadcastCollector<G, T> {
+    /// Verify the signature of `broadcast` against the key of its sender in `members_pks`, and
+    /// record its message as `insert` does. If the sender already signed a different message,
+    /// an `EquivocationProof` is recorded as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidSenderIndex` if `members_pks` has no key for the sender, and
+    /// `InvalidSignature` if the signature does not validate, in which case the message is
+    /// discarded. Otherwise, returns the same errors as `insert`.
+    pub fn insert_signed(
+        &mut self,
+        members_pks: &[MemberCommunicationPublicKey<G>],
+        broadcast: SignedBroadcast<G, T>,
+    ) -> Result<(), DkgError> {
+        let sender_index = broadcast.sender_index();
+        if sender_index == 0 || sender_index > self.environment.nr_members {
+            return Err(DkgError::InvalidSenderIndex(sender_index));
+        }
+        let sender_pk = members_pks
+            .get(sender_index - 1)
+            .ok_or(DkgError::InvalidSenderIndex(sender_index))?;
+        broadcast.verify(&self.environment, sender_pk)?;
+
+        let signed_digest = broadcast.signed_digest().clone();
+        match &self.signed_digests[sender_index - 1] {
+            Some(recorded) => {
+                if let Some(proof) = EquivocationProof::new(recorded.clone(), signed_digest) {
+                    if !self.equivocators.contains(&sender_index) {
+                        self.equivocation_proofs.push(proof);
+                    }
+                }
+            }
+            None => self.signed_digests[sender_index - 1] = Some(signed_digest),
+        }
+
+        self.insert(sender_index, broadcast.message)
+    }
+
+    /// Returns the echo message of the phase, containing the signed digests received from
+    /// every member.
+    pub fn echo(&self) -> EchoBroadcast<G> {
+        EchoBroadcast::new(T::PHASE, self.signed_digests.clone())
+    }
+
+    /// Returns the proofs of equivocation of the members that signed conflicting messages.
+    pub fn equivocation_proofs(&self) -> &[EquivocationProof<G>] {
+        &self.equivocation_proofs
+    }
+}
+
+impl<G: PrimeGroupElement> BroadcastCollector1<G> {
+    /// Produce the fetched state of phase 1. This should be called once the phase deadline
+    /// has passed.
+    pub fn fetched_state(&self) -> Vec<MembersFetchedState1<G>> {
+        MembersFetchedState1::from_broadcast(
+            &self.environment,
+            self.recipient_index,
+            &self.others(),
+        )
+    }
+}
+
+impl<G: PrimeGroupElement> BroadcastCollector2<G> {
+    /// Produce the fetched state of phase 2. This should be called once the phase deadline
+    /// has passed.
+    pub fn fetched_state(&self) -> Vec<MembersFetchedState2<G>> {
+        MembersFetchedState2::from_broadcast(
+            &self.environment,
+            self.recipient_index,
+            &self.others(),
+        )
+    }
+}
+
+impl<G: PrimeGroupElement> BroadcastCollector3<G> {
+    /// Produce the fetched state of phase 3. This should be called once the phase deadline
+    /// has passed.
+    pub fn fetched_state(&self) -> Vec<MembersFetchedState3<G>> {
+        MembersFetchedState3::from_broadcast(
+            &self.environment,
+            self.recipient_index,
+            &self.others(),
+        )
+    }
+}
+
+impl<G: PrimeGroupElement> BroadcastCollector4<G> {
+    /// Produce the fetched state of phase 4. This should be called once the phase deadline
+    /// has passed.
+    pub fn fetched_state(&self) -> Vec<MembersFetchedState4<G>> {
+        MembersFetchedState4::from_broadcast(
+            &self.environment,
+            self.recipient_index,
+            &self.others(),
+        )
+    }
+}
+
+impl<G: PrimeGroupElement> BroadcastCollector5<G> {
+    /// Produce the fetched state of phase 5. This should be called once the phase deadline
+    /// has passed.
+    pub fn fetched_state(&self) -> Vec<MembersFetchedState5<G>> {
+        MembersFetchedState5::from_broadcast(
+            &self.environment,
+            self.recipient_index,
+            &self.others(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dkg::committee::DistributedKeyGeneration;
+    use crate::dkg::procedure_keys::MemberCommunicationKey;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use rand_core::OsRng;
+
+    #[test]
+    fn unordered_ingestion() {
+        let mut rng = OsRng;
+
+        let shared_string = b"Example of a shared string.".to_owned();
+        let threshold = 1;
+        let nr_members = 3;
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
+
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
+        let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
+
+        let (m1, broad_1) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
+        let (_m2, broad_2) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
+        let (_m3, broad_3) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
+
+        // Party 1 receives the messages in reverse order, together with its own message.
+        let mut collector = BroadcastCollector1::new(&environment, 1);
+        assert!(collector.insert(3, broad_3.clone()).is_ok());
+        assert!(collector.insert(1, broad_1.clone()).is_ok());
+        assert!(collector.insert(2, broad_2.clone()).is_ok());
+
+        assert_eq!(
+            collector.others(),
+            vec![Some(broad_2.clone()), Some(broad_3.clone())]
+        );
+        assert_eq!(
+            collector.broadcasts(),
+            vec![Some(broad_1), Some(broad_2), Some(broad_3)]
+        );
+
+        let (phase_2, broadcast_data) = m1.proceed(&collector.fetched_state(), &mut rng);
+        assert!(phase_2.is_ok());
+        assert!(broadcast_data.is_none());
+    }
+
+    #[test]
+    fn duplicates_and_equivocation() {
+        let mut rng = OsRng;
+
+        let shared_string = b"Example of a shared string.".to_owned();
+        let threshold = 1;
+        let nr_members = 3;
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
+
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
+        let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
+
+        let (_m2, broad_2) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
+        let (_m3, broad_3) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
+        let (_m3, other_broad_3) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
+
+        let mut collector = BroadcastCollector1::new(&environment, 1);
+        assert_eq!(
+            collector.insert(4, broad_2.clone()),
+            Err(DkgError::InvalidSenderIndex(4))
+        );
+        assert!(collector.insert(2, broad_2.clone()).is_ok());
+        assert_eq!(
+            collector.insert(2, broad_2.clone()),
+            Err(DkgError::DuplicateBroadcast(2))
+        );
+
+        // Party 3 publishes two different messages.
+        assert!(collector.insert(3, broad_3).is_ok());
+        assert_eq!(
+            collector.insert(3, other_broad_3.clone()),
+            Err(DkgError::EquivocatingBroadcast(3))
+        );
+        assert_eq!(
+            collector.insert(3, other_broad_3),
+            Err(DkgError::EquivocatingBroadcast(3))
+        );
+
+        assert_eq!(collector.equivocators(), &[3]);
+        assert_eq!(collector.others(), vec![Some(broad_2), None]);
+    }
+
+    #[test]
+    fn signed_ingestion() {
+        let mut rng = OsRng;
+
+        let shared_string = b"Example of a shared string.".to_owned();
+        let threshold = 1;
+        let nr_members = 3;
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
+
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
+        let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
+
+        let (_m2, broad_2) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
+        let (_m3, broad_3) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
+        let (_m3, other_broad_3) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
+
+        let signed_2 = SignedBroadcast::sign(&environment, 2, broad_2.clone(), mc2, &mut rng);
+        let signed_3 = SignedBroadcast::sign(&environment, 3, broad_3, mc3, &mut rng);
+        let other_signed_3 = SignedBroadcast::sign(&environment, 3, other_broad_3, mc3, &mut rng);
+        // Party 2 signs a message claiming to be party 3.
+        let forged_3 = SignedBroadcast::sign(&environment, 3, broad_2.clone(), mc2, &mut rng);
+
+        let mut collector = BroadcastCollector1::new(&environment, 1);
+        // The sender has no key in a shorter list of public keys.
+        assert_eq!(
+            collector.insert_signed(&mc[..2], signed_3.clone()),
+            Err(DkgError::InvalidSenderIndex(3))
+        );
+        assert_eq!(
+            collector.insert_signed(&mc, forged_3),
+            Err(DkgError::InvalidSignature)
+        );
+        assert!(collector.insert_signed(&mc, signed_3).is_ok());
+        assert!(collector.insert_signed(&mc, signed_2).is_ok());
+        assert_eq!(
+            collector.insert_signed(&mc, other_signed_3),
+            Err(DkgError::EquivocatingBroadcast(3))
+        );
+
+        assert_eq!(collector.others(), vec![Some(broad_2), None]);
+        assert_eq!(collector.equivocation_proofs().len(), 1);
+        assert_eq!(collector.equivocation_proofs()[0].accused_index(), 3);
+        assert!(collector.equivocation_proofs()[0]
+            .verify(&environment, &mc)
+            .is_ok());
+        assert_eq!(collector.echo().views.len(), nr_members);
+    }
+}
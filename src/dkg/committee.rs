@@ -6,15 +6,19 @@ use super::procedure_keys::{
 };
 use crate::cryptography::commitment::CommitmentKey;
 use crate::cryptography::elgamal::{PublicKey, SecretKey};
+use crate::cryptography::schnorr::SchnorrZkp;
 use crate::dkg::broadcast::{
-    BroadcastPhase1, BroadcastPhase2, BroadcastPhase3, BroadcastPhase4, BroadcastPhase5,
-    MisbehavingPartiesRound1, MisbehavingPartiesRound3, MisbehavingPartiesRound4,
-    ProofOfMisbehaviour,
+    prove_constant_term, verified_messages, verify_constant_term_proof, BroadcastPhase1,
+    BroadcastPhase2, BroadcastPhase3, BroadcastPhase4, BroadcastPhase5, MisbehavingPartiesRound1,
+    MisbehavingPartiesRound3, MisbehavingPartiesRound4, ProofOfMisbehaviour, SignedBroadcast,
 };
+use crate::dkg::equivocation::EquivocationProof;
 use crate::dkg::procedure_keys::MasterPublicKey;
 use crate::errors::DkgError;
-use crate::polynomial::{lagrange_interpolation, Polynomial};
-use crate::traits::{PrimeGroupElement, Scalar};
+use crate::parallel;
+use crate::polynomial::{LagrangeBasis, Polynomial};
+use crate::traits::{Parallelisable, PrimeGroupElement, Scalar};
+use crate::vss::{self, feldman, pedersen};
 use rand_core::{CryptoRng, RngCore};
 use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
@@ -23,12 +27,14 @@ use std::marker::PhantomData;
 #[derive(Clone, Debug, PartialEq)]
 pub struct Environment<G: PrimeGroupElement> {
     pub(crate) threshold: usize,
-    nr_members: usize,
+    pub(crate) nr_members: usize,
     pub(crate) commitment_key: CommitmentKey<G>,
+    pub(crate) session_id: Vec<u8>,
 }
 
-/// Private state, generated over the protocol
-#[derive(Clone, Debug, PartialEq)]
+/// Private state, generated over the protocol. Its `Debug` representation omits the secret
+/// values, which can be displayed with `danger_debug`.
+#[derive(Clone, PartialEq)]
 pub struct IndividualState<G: PrimeGroupElement> {
     index: usize,
     environment: Environment<G>,
@@ -39,6 +45,8 @@ pub struct IndividualState<G: PrimeGroupElement> {
     master_public_key: Option<MemberPublicShare<G>>,
     indexed_received_shares: Vec<Option<DecryptedShares<G>>>,
     indexed_committed_shares: Vec<Option<Vec<G>>>,
+    /// Proof of knowledge of the constant term of the member's sharing polynomial
+    constant_term_proof: SchnorrZkp<G>,
     /// Set of parties whose secret needs to be reconstructed
     reconstructable_set: Vec<usize>,
     qualified_set: Vec<usize>,
@@ -50,12 +58,81 @@ pub struct Phases<G: PrimeGroupElement, Phase> {
     phase: PhantomData<Phase>,
 }
 
+impl<G: PrimeGroupElement> Debug for IndividualState<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndividualState")
+            .field("index", &self.index)
+            .field("environment", &self.environment)
+            .field("members_pks", &self.members_pks)
+            .field("public_share", &self.public_share)
+            .field("master_public_key", &self.master_public_key)
+            .field("indexed_committed_shares", &self.indexed_committed_shares)
+            .field("constant_term_proof", &self.constant_term_proof)
+            .field("reconstructable_set", &self.reconstructable_set)
+            .field("qualified_set", &self.qualified_set)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: PrimeGroupElement> IndividualState<G> {
+    /// Debug representation of the state which, unlike `Debug`, includes the communication
+    /// secret key, the final secret share and the received shares. Handle the output with
+    /// care, as it discloses the secrets of the member.
+    pub fn danger_debug(&self) -> String {
+        let received_shares: Vec<Option<String>> = self
+            .indexed_received_shares
+            .iter()
+            .map(|shares| shares.as_ref().map(DecryptedShares::danger_debug))
+            .collect();
+        format!(
+            "IndividualState {{ index: {:?}, environment: {:?}, communication_sk: {}, \
+             members_pks: {:?}, final_share: {:?}, public_share: {:?}, master_public_key: {:?}, \
+             indexed_received_shares: {:?}, indexed_committed_shares: {:?}, \
+             constant_term_proof: {:?}, reconstructable_set: {:?}, qualified_set: {:?} }}",
+            self.index,
+            self.environment,
+            self.communication_sk.danger_debug(),
+            self.members_pks,
+            self.final_share
+                .as_ref()
+                .map(MemberSecretShare::danger_debug),
+            self.public_share,
+            self.master_public_key,
+            received_shares,
+            self.indexed_committed_shares,
+            self.constant_term_proof,
+            self.reconstructable_set,
+            self.qualified_set,
+        )
+    }
+}
+
+/// Cloning a member state copies its secrets, so it is only available in the tests and with
+/// the `bench` feature, for the benchmarks to run a phase repeatedly from the same state.
+#[cfg(any(test, feature = "bench"))]
+impl<G: PrimeGroupElement, P> Clone for Phases<G, P> {
+    fn clone(&self) -> Self {
+        Phases {
+            state: self.state.clone(),
+            phase: PhantomData,
+        }
+    }
+}
+
 impl<G: PrimeGroupElement, P> Debug for Phases<G, P> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Phase").field("state", &self.state).finish()
     }
 }
 
+impl<G: PrimeGroupElement, P> Phases<G, P> {
+    /// Debug representation of the phase which, unlike `Debug`, includes the secret values of
+    /// the member's state. Handle the output with care, as it discloses the member's secrets.
+    pub fn danger_debug(&self) -> String {
+        format!("Phase {{ state: {} }}", self.state.danger_debug())
+    }
+}
+
 impl<G: PrimeGroupElement, P> PartialEq for Phases<G, P> {
     fn eq(&self, other: &Self) -> bool {
         self.state == other.state
@@ -64,12 +141,21 @@ impl<G: PrimeGroupElement, P> PartialEq for Phases<G, P> {
 
 impl<G: PrimeGroupElement> Environment<G> {
     /// Initialise the Distributed Key Generation environment. As input is given the `threshold`,
-    /// `nr_members` and the bytes used to generated the commitment key, `ck_gen_bytes`.
+    /// `nr_members`, the bytes used to generated the commitment key, `ck_gen_bytes`, and the
+    /// identifier of this execution of the protocol, `session_id`, to which signed broadcasts
+    /// are bound. The session identifier must be different for every execution, e.g. agreed
+    /// at random by the members, so that signed messages of one execution cannot be replayed
+    /// in another with the same commitment key.
     ///
     /// # Panics
     ///
     /// Panics if `threshold` is greater or equal to `nr_members / 2`.
-    pub fn init(threshold: usize, nr_members: usize, ck_gen_bytes: &[u8]) -> Self {
+    pub fn init(
+        threshold: usize,
+        nr_members: usize,
+        ck_gen_bytes: &[u8],
+        session_id: &[u8],
+    ) -> Self {
         assert!(threshold < (nr_members + 1) / 2);
 
         let commitment_key = CommitmentKey::generate(ck_gen_bytes);
@@ -78,6 +164,7 @@ impl<G: PrimeGroupElement> Environment<G> {
             threshold,
             nr_members,
             commitment_key,
+            session_id: session_id.to_vec(),
         }
     }
 }
@@ -97,7 +184,11 @@ pub struct Phase4 {}
 #[doc(hidden)]
 pub struct Phase5 {}
 
-impl<G: PrimeGroupElement> Phases<G, Initialise> {
+impl<G: PrimeGroupElement> Phases<G, Initialise>
+where
+    G: Parallelisable,
+    G::CorrespondingScalar: Parallelisable,
+{
     /// Generate a new member state from random. This is round 1 of the protocol. Receives as
     /// input the `environment`, the initializer's private communication key, `secret_key`,
     /// the participants public keys, `committee_pks`, and the initializer's index `my`.
@@ -145,44 +236,55 @@ impl<G: PrimeGroupElement> Phases<G, Initialise> {
         let sharing_polynomial =
             Polynomial::<G::CorrespondingScalar>::random(rng, environment.threshold);
 
-        let mut apubs = Vec::with_capacity(environment.threshold + 1);
-        let mut coeff_comms = Vec::with_capacity(environment.threshold + 1);
+        let apubs = feldman::Dealing::<G>::commit(&sharing_polynomial).into_commitments();
+        let constant_term_proof =
+            prove_constant_term(environment, my, &sharing_polynomial.at_zero(), rng);
+        let coeff_comms = pedersen::Dealing::commit(
+            &environment.commitment_key,
+            &sharing_polynomial,
+            &hiding_polynomial,
+        )
+        .into_commitments();
 
-        for (ai, &bi) in sharing_polynomial
-            .get_coefficients()
-            .zip(hiding_polynomial.get_coefficients())
-        {
-            let apub = G::generator() * *ai;
-            let coeff_comm = (environment.commitment_key.h * bi) + apub;
-            apubs.push(apub);
-            coeff_comms.push(coeff_comm);
-        }
+        let indices: Vec<G::CorrespondingScalar> = (1..=environment.nr_members)
+            .map(|i| <G::CorrespondingScalar as Scalar>::from_u64(i as u64))
+            .collect();
+        let shares = sharing_polynomial.evaluate_many(&indices);
+        let randomnesses = hiding_polynomial.evaluate_many(&indices);
 
-        let mut encrypted_shares: Vec<EncryptedShares<G>> =
-            Vec::with_capacity(environment.nr_members - 1);
-        #[allow(clippy::needless_range_loop)]
+        // The randomness of the encryptions is sampled ahead, in the order in which the shares
+        // are encrypted, so that the broadcast message does not depend on whether the
+        // encryptions are computed in parallel.
+        let mut recipients = Vec::with_capacity(environment.nr_members);
         for i in 0..environment.nr_members {
-            let idx = <G::CorrespondingScalar as Scalar>::from_u64((i + 1) as u64);
-            let randomness = hiding_polynomial.evaluate(&idx);
-            let share = sharing_polynomial.evaluate(&idx);
-
-            let pk = &ordered_pks[i];
+            let randomness_r = G::CorrespondingScalar::random(rng);
+            let share_r = G::CorrespondingScalar::random(rng);
+            recipients.push((i, randomness_r, share_r));
+        }
 
-            let encrypted_randomness = pk.hybrid_encrypt(&randomness.to_bytes(), rng);
-            let encrypted_share = pk.hybrid_encrypt(&share.to_bytes(), rng);
+        let encrypted_shares: Vec<EncryptedShares<G>> =
+            parallel::map(&recipients, |(i, randomness_r, share_r)| {
+                let pk = &ordered_pks[*i];
 
-            encrypted_shares.push(EncryptedShares {
-                recipient_index: i + 1,
-                encrypted_share,
-                encrypted_randomness,
+                EncryptedShares {
+                    recipient_index: i + 1,
+                    encrypted_share: pk.hybrid_encrypt_with_r(&shares[*i].to_bytes(), share_r),
+                    encrypted_randomness: pk
+                        .hybrid_encrypt_with_r(&randomnesses[*i].to_bytes(), randomness_r),
+                }
             });
-            if i == my - 1 {
-                decrypted_shares[my - 1] = Some(DecryptedShares {
-                    decrypted_share: share,
-                    decrypted_randomness: randomness,
-                    committed_coefficients: coeff_comms.clone(),
-                });
-            }
+
+        decrypted_shares[my - 1] = Some(DecryptedShares {
+            decrypted_share: shares[my - 1],
+            decrypted_randomness: randomnesses[my - 1],
+            committed_coefficients: coeff_comms.clone(),
+        });
+
+        #[cfg(feature = "zeroize")]
+        {
+            let (mut shares, mut randomnesses) = (shares, randomnesses);
+            shares.iter_mut().for_each(Scalar::erase);
+            randomnesses.iter_mut().for_each(Scalar::erase);
         }
 
         let qualified_set = vec![1; environment.nr_members];
@@ -199,6 +301,7 @@ impl<G: PrimeGroupElement> Phases<G, Initialise> {
             master_public_key: None,
             indexed_received_shares: decrypted_shares,
             indexed_committed_shares: committed_shares,
+            constant_term_proof,
             reconstructable_set,
             qualified_set,
         };
@@ -216,7 +319,11 @@ impl<G: PrimeGroupElement> Phases<G, Initialise> {
     }
 }
 
-impl<G: PrimeGroupElement> Phases<G, Phase1> {
+impl<G: PrimeGroupElement> Phases<G, Phase1>
+where
+    G: Parallelisable,
+    G::CorrespondingScalar: Parallelisable,
+{
     /// Function that takes as input the broadcast data from the initialisation and proceeds to
     /// phase 1.
     pub fn proceed_with_broadcast<R>(
@@ -270,55 +377,43 @@ impl<G: PrimeGroupElement> Phases<G, Phase1> {
     {
         let mut qualified_set = self.state.qualified_set.clone();
         let mut misbehaving_parties: Vec<MisbehavingPartiesRound1<G>> = Vec::new();
-        for fetched_data in members_state {
-            if let (Some(indexed_shares), Some(commited_coeffs)) =
-                fetched_data.get_shares_and_coeffs()
-            {
+        // Shares which decrypted correctly, together with the sender index and the encrypted
+        // shares, required to generate a proof of misbehaviour if the check fails.
+        let mut received_shares: Vec<(usize, EncryptedShares<G>, DecryptedShares<G>)> =
+            Vec::with_capacity(members_state.len());
+        // Decryption of the shares of every dealer, which are then processed in order.
+        let communication_sk = &self.state.communication_sk;
+        let decryptions = parallel::map(members_state, |fetched_data| {
+            fetched_data
+                .indexed_shares
+                .as_ref()
+                .map(|indexed_shares| communication_sk.decrypt_shares(indexed_shares))
+        });
+        for (fetched_data, decryption) in members_state.iter().zip(decryptions) {
+            if let (Some(indexed_shares), Some(commited_coeffs), Some(decryption)) = (
+                fetched_data.indexed_shares.clone(),
+                fetched_data.committed_coeffs.clone(),
+                decryption,
+            ) {
                 // If recipient indexed does not correspond with ones self, abort.
                 if indexed_shares.recipient_index != self.state.index {
                     return (Err(DkgError::FetchedInvalidData), None);
                 }
 
-                if let (Some(decrypted_share), Some(decrypted_randomness)) = self
-                    .state
-                    .communication_sk
-                    .decrypt_shares(indexed_shares.clone())
-                {
-                    let index_pow =
-                        <G::CorrespondingScalar as Scalar>::from_u64(self.state.index as u64)
-                            .exp_iter()
-                            .take(self.state.environment.threshold + 1);
-
-                    let check_element = self.state.environment.commitment_key.h
-                        * decrypted_randomness
-                        + G::generator() * decrypted_share;
-                    let multi_scalar =
-                        G::vartime_multiscalar_multiplication(index_pow, commited_coeffs.clone());
-
-                    self.state.indexed_received_shares[fetched_data.sender_index - 1] =
-                        Some(DecryptedShares {
+                if let (Some(decrypted_share), Some(decrypted_randomness)) = decryption {
+                    received_shares.push((
+                        fetched_data.sender_index,
+                        indexed_shares,
+                        DecryptedShares {
                             decrypted_share,
                             decrypted_randomness,
                             committed_coefficients: commited_coeffs,
-                        });
-
-                    if check_element != multi_scalar {
-                        let proof = ProofOfMisbehaviour::generate(
-                            &indexed_shares.clone(),
-                            &self.state.communication_sk,
-                            rng,
-                        );
-                        qualified_set[fetched_data.sender_index - 1] = 0;
-                        misbehaving_parties.push(MisbehavingPartiesRound1 {
-                            accused_index: fetched_data.sender_index,
-                            accusation_error: DkgError::ShareValidityFailed,
-                            proof_accusation: proof,
-                        });
-                    }
+                        },
+                    ));
                 } else {
                     // todo: handle the proofs. Might not be the most optimal way of handling these two
                     let proof = ProofOfMisbehaviour::generate(
-                        &indexed_shares.clone(),
+                        &indexed_shares,
                         &self.state.communication_sk,
                         rng,
                     );
@@ -337,6 +432,37 @@ impl<G: PrimeGroupElement> Phases<G, Phase1> {
             }
         }
 
+        // We first check all shares at once. Only if the batched check fails we verify the
+        // shares of every dealer individually, to identify the misbehaving ones.
+        let all_shares: Vec<&DecryptedShares<G>> = received_shares
+            .iter()
+            .map(|(_, _, shares)| shares)
+            .collect();
+        let all_valid = DecryptedShares::batch_verify(
+            &self.state.environment,
+            self.state.index,
+            &all_shares,
+            rng,
+        );
+
+        for (sender_index, indexed_shares, decrypted_shares) in received_shares {
+            if !all_valid && !decrypted_shares.verify(&self.state.environment, self.state.index) {
+                let proof = ProofOfMisbehaviour::generate(
+                    &indexed_shares,
+                    &self.state.communication_sk,
+                    rng,
+                );
+                qualified_set[sender_index - 1] = 0;
+                misbehaving_parties.push(MisbehavingPartiesRound1 {
+                    accused_index: sender_index,
+                    accusation_error: DkgError::ShareValidityFailed,
+                    proof_accusation: proof,
+                });
+            }
+            self.state.indexed_received_shares[sender_index - 1] = Some(decrypted_shares);
+        }
+        misbehaving_parties.sort_by_key(|misbehaving| misbehaving.accused_index);
+
         if misbehaving_parties.len() > self.state.environment.threshold {
             return (
                 Err(DkgError::MisbehaviourHigherThreshold),
@@ -371,7 +497,19 @@ impl<G: PrimeGroupElement> Phases<G, Phase2> {
         &mut self,
         broadcast_complaints: &[MembersFetchedState2<G>],
         broadcast_phase_1: &[Option<BroadcastPhase1<G>>],
+        equivocation_proofs: &[EquivocationProof<G>],
     ) {
+        // A valid proof of equivocation disqualifies the sender. This is the only step in which
+        // the qualified set is computed, so proofs gathered after this phase have no effect.
+        for proof in equivocation_proofs {
+            if proof
+                .verify(&self.state.environment, &self.state.members_pks)
+                .is_ok()
+            {
+                self.state.qualified_set[proof.accused_index() - 1] &= 0;
+            }
+        }
+
         for broadcast in broadcast_complaints {
             for misbehaving_parties in &broadcast.accusations.misbehaving_parties {
                 // if the accused party did not public in the first round, we ignore it
@@ -431,7 +569,7 @@ impl<G: PrimeGroupElement> Phases<G, Phase2> {
     /// If there is less qualified members than the threshold, the function fails and does not
     /// proceed to the following phase.
     pub fn proceed(
-        mut self,
+        self,
         broadcast_complaints: &[MembersFetchedState2<G>],
         // to verify the complaints, we need to have access to the broadcast messages of phase 1
         broadcast_round_1: &[Option<BroadcastPhase1<G>>],
@@ -439,7 +577,29 @@ impl<G: PrimeGroupElement> Phases<G, Phase2> {
         Result<Phases<G, Phase3>, DkgError>,
         Option<BroadcastPhase3<G>>,
     ) {
-        self.compute_qualified_set(broadcast_complaints, broadcast_round_1);
+        self.proceed_with_equivocations(broadcast_complaints, broadcast_round_1, &[])
+    }
+
+    /// Same as `proceed`, but additionally takes as input the proofs of equivocation gathered
+    /// in the echo steps, `equivocation_proofs`. Every member against which a valid proof of
+    /// equivocation exists is removed from the qualified set. As the qualified set is final
+    /// after this phase, equivocations need to be detected by then, i.e. in the echoes of
+    /// phases 1 and 2, to disqualify the sender. The later phases do not take such proofs.
+    ///
+    /// Errors
+    ///
+    /// If there is less qualified members than the threshold, the function fails and does not
+    /// proceed to the following phase.
+    pub fn proceed_with_equivocations(
+        mut self,
+        broadcast_complaints: &[MembersFetchedState2<G>],
+        broadcast_round_1: &[Option<BroadcastPhase1<G>>],
+        equivocation_proofs: &[EquivocationProof<G>],
+    ) -> (
+        Result<Phases<G, Phase3>, DkgError>,
+        Option<BroadcastPhase3<G>>,
+    ) {
+        self.compute_qualified_set(broadcast_complaints, broadcast_round_1, equivocation_proofs);
         if self.state.qualified_set.len() < self.state.environment.threshold + 1 {
             return (Err(DkgError::MisbehaviourHigherThreshold), None);
         }
@@ -448,6 +608,7 @@ impl<G: PrimeGroupElement> Phases<G, Phase2> {
             committed_coefficients: self.state.indexed_committed_shares[self.state.index - 1]
                 .clone()
                 .expect("owns committed coefficient is always existent"),
+            proof_of_knowledge: self.state.constant_term_proof.clone(),
         });
 
         // Now that we have the qualified set, we can compute the secret share
@@ -462,7 +623,7 @@ impl<G: PrimeGroupElement> Phases<G, Phase2> {
         }
 
         self.state.public_share = Some(MemberPublicShare(PublicKey {
-            pk: G::generator() * secret_share,
+            pk: G::generator_mul(&secret_share),
         }));
         self.state.final_share = Some(MemberSecretShare(SecretKey { sk: secret_share }));
 
@@ -476,7 +637,11 @@ impl<G: PrimeGroupElement> Phases<G, Phase2> {
     }
 }
 
-impl<G: PrimeGroupElement> Phases<G, Phase3> {
+impl<G: PrimeGroupElement> Phases<G, Phase3>
+where
+    G: Parallelisable,
+    G::CorrespondingScalar: Parallelisable,
+{
     /// Function that takes as input the broadcast data from the previous phase and proceeds to
     /// phase 4.
     pub fn proceed_with_broadcast(
@@ -498,7 +663,8 @@ impl<G: PrimeGroupElement> Phases<G, Phase3> {
     /// Each participant fetches the commitment of the polynomials of the previous round, and
     /// verifies that it corresponds with the polynomial committed initially. In particular player
     /// $i$ checks that
-    /// \\(g^{s_{j,i}} = \prod_{l = 0}^tA_{j,l}^{i^l} \\). If the check fails it publishes a proof
+    /// \\(g^{s_{j,i}} = \prod_{l = 0}^tA_{j,l}^{i^l} \\), and that the proof of knowledge of the
+    /// secret behind \\( A_{j,0} \\) is valid. If any of the checks fails it publishes a proof
     /// of misbehaviour.
     ///
     /// Errors
@@ -514,14 +680,43 @@ impl<G: PrimeGroupElement> Phases<G, Phase3> {
     ) {
         let mut honest = vec![0usize; self.state.environment.nr_members];
         honest[self.state.index - 1] |= 1; /* self is considered honest */
-        let received_shares = self.state.indexed_received_shares.clone();
         let mut misbehaving_parties: Vec<MisbehavingPartiesRound3<G>> = Vec::new();
 
-        for fetched_commitments in fetched_state_3 {
+        // Check of the shares of every qualified dealer against its committed coefficients.
+        let state = &self.state;
+        let valid_shares = parallel::map(fetched_state_3, |fetched_commitments| {
+            let sender_index = fetched_commitments.sender_index;
+            match (
+                &fetched_commitments.committed_coefficients,
+                &fetched_commitments.proof_of_knowledge,
+                &state.indexed_received_shares[sender_index - 1],
+            ) {
+                (Some(coefficients), Some(proof), Some(indexed_shares))
+                    if state.qualified_set[sender_index - 1] != 0 =>
+                {
+                    let share = vss::Share {
+                        index: state.index,
+                        value: indexed_shares.decrypted_share,
+                    };
+                    feldman::verify_share(state.environment.threshold, coefficients, &share)
+                        && verify_constant_term_proof(
+                            &state.environment,
+                            sender_index,
+                            coefficients,
+                            proof,
+                        )
+                        .is_ok()
+                }
+                _ => false,
+            }
+        });
+
+        for (fetched_commitments, valid) in fetched_state_3.iter().zip(valid_shares) {
             // if the fetched commitment is from a disqualified player, we skip
             if self.state.qualified_set[fetched_commitments.sender_index - 1] != 0 {
-                let indexed_shares = received_shares[fetched_commitments.sender_index - 1]
-                    .clone()
+                let indexed_shares = self.state.indexed_received_shares
+                    [fetched_commitments.sender_index - 1]
+                    .as_ref()
                     .expect("If it is part of honest members, their shares should be recorded");
                 // We proceed only if there are coefficients.
                 if let Some(coefficients) = &fetched_commitments.committed_coefficients {
@@ -529,16 +724,7 @@ impl<G: PrimeGroupElement> Phases<G, Phase3> {
                     self.state.indexed_committed_shares[fetched_commitments.sender_index - 1] =
                         Some(coefficients.clone());
 
-                    let index_pow =
-                        <G::CorrespondingScalar as Scalar>::from_u64(self.state.index as u64)
-                            .exp_iter()
-                            .take(self.state.environment.threshold + 1);
-
-                    let check_element = G::generator() * indexed_shares.decrypted_share;
-                    let multi_scalar =
-                        G::vartime_multiscalar_multiplication(index_pow, coefficients.clone());
-
-                    if check_element != multi_scalar {
+                    if !valid {
                         misbehaving_parties.push(MisbehavingPartiesRound3 {
                             accused_index: fetched_commitments.sender_index,
                             decrypted_share: indexed_shares.decrypted_share,
@@ -632,26 +818,33 @@ impl<G: PrimeGroupElement> Phases<G, Phase4> {
         // misbehaving parties will have their shares disclosed to generate the master public key
         let mut reconstruct_shares: Vec<Option<MisbehavingPartiesRound4<G>>> =
             vec![None; self.state.environment.nr_members];
-        let received_shares = self.state.indexed_received_shares.clone();
 
         for fetched_complaints in broadcast_complaints {
             // If accused party is disqualified, we ignore it
             if self.state.qualified_set[fetched_complaints.misbehaving_party.accused_index - 1] != 0
             {
                 // Now we verify that the complaint is valid. If the broadcast of phase 3
-                // is None, then the complaint is valid.
+                // is None, or its proof of knowledge does not validate, then the complaint
+                // is valid.
                 if let Some(broadcast_phase_3) = fetched_complaints.accused_broadcast_phase_3 {
-                    if fetched_complaints
-                        .misbehaving_party
-                        .verify(
+                    let valid_proof = broadcast_phase_3
+                        .verify_proof_of_knowledge(
                             &self.state.environment,
-                            fetched_complaints.accuser_index,
-                            &fetched_complaints
-                                .accused_broadcast_phase_1
-                                .committed_coefficients,
-                            &broadcast_phase_3.committed_coefficients,
+                            fetched_complaints.misbehaving_party.accused_index,
                         )
-                        .is_err()
+                        .is_ok();
+                    if valid_proof
+                        && fetched_complaints
+                            .misbehaving_party
+                            .verify(
+                                &self.state.environment,
+                                fetched_complaints.accuser_index,
+                                &fetched_complaints
+                                    .accused_broadcast_phase_1
+                                    .committed_coefficients,
+                                &broadcast_phase_3.committed_coefficients,
+                            )
+                            .is_err()
                     {
                         // todo: what do we do? for the moment, we simply ignore the complaint
                         continue;
@@ -659,7 +852,7 @@ impl<G: PrimeGroupElement> Phases<G, Phase4> {
                 }
                 // If the tests pass, then we disclose the shares of the misbehaving party,
                 // and include it in the data we will broadcast.
-                let indexed_shares = received_shares
+                let indexed_shares = self.state.indexed_received_shares
                     [fetched_complaints.misbehaving_party.accused_index - 1]
                     .as_ref()
                     .expect("If it is part of honest members, their shares should be recorded");
@@ -742,6 +935,10 @@ impl<G: PrimeGroupElement> Phases<G, Phase5> {
 
         let received_shares = self.state.indexed_received_shares;
 
+        // The secrets of the misbehaving parties are usually reconstructed over the same set of
+        // indices, so we keep the last computed lagrange basis to reuse its coefficients.
+        let mut lagrange_basis: Option<LagrangeBasis<G::CorrespondingScalar>> = None;
+
         for i in 0..self.state.environment.nr_members {
             if self.state.reconstructable_set[i] == 1 && self.state.qualified_set[i] != 1 {
                 panic!("Only qualified members should be reconstructed");
@@ -781,12 +978,13 @@ impl<G: PrimeGroupElement> Phases<G, Phase5> {
                 }
 
                 // If we have sufficient, then we interpolate at zero
-                let recovered_secret = lagrange_interpolation(
-                    G::CorrespondingScalar::zero(),
-                    &evaluated_points,
-                    &indices,
-                );
-                master_key = master_key + G::generator() * recovered_secret;
+                let basis = match lagrange_basis {
+                    Some(basis) if basis.indices() == indices.as_slice() => basis,
+                    _ => LagrangeBasis::new(G::CorrespondingScalar::zero(), &indices),
+                };
+                let recovered_secret = basis.interpolate(&evaluated_points);
+                lagrange_basis = Some(basis);
+                master_key = master_key + G::generator_mul(&recovered_secret);
             } else {
                 master_key = master_key
                     + committed_shares[i]
@@ -816,9 +1014,6 @@ pub struct MembersFetchedState1<G: PrimeGroupElement> {
 }
 
 impl<G: PrimeGroupElement> MembersFetchedState1<G> {
-    fn get_shares_and_coeffs(&self) -> (Option<EncryptedShares<G>>, Option<Vec<G>>) {
-        (self.indexed_shares.clone(), self.committed_coeffs.clone())
-    }
     /// Given as input all broadcast messages in an ordered vector, returns a vector of indexed
     /// fetched states. If some party does not broadcast in Round 1, then the entry should be
     /// filled with `None`. The broadcast messages must be ordered from low index to high index.
@@ -869,6 +1064,24 @@ impl<G: PrimeGroupElement> MembersFetchedState1<G> {
         }
         output
     }
+
+    /// Same as `from_broadcast`, but takes as input the signed broadcast messages. Messages
+    /// whose signature does not validate under the key of the sender in `members_pks` are
+    /// discarded, as if the sender did not broadcast.
+    pub fn from_signed_broadcast(
+        environment: &Environment<G>,
+        recipient_index: usize,
+        members_pks: &[MemberCommunicationPublicKey<G>],
+        broadcast_messages: &[Option<SignedBroadcast<G, BroadcastPhase1<G>>>],
+    ) -> Vec<Self> {
+        let messages = verified_messages(
+            environment,
+            recipient_index,
+            members_pks,
+            broadcast_messages,
+        );
+        Self::from_broadcast(environment, recipient_index, &messages)
+    }
 }
 
 /// State of the members after round 1. This structure contains the indexed encrypted
@@ -906,6 +1119,24 @@ impl<G: PrimeGroupElement> MembersFetchedState2<G> {
         }
         output
     }
+
+    /// Same as `from_broadcast`, but takes as input the signed broadcast messages. Messages
+    /// whose signature does not validate under the key of the sender in `members_pks` are
+    /// discarded, as if the sender did not broadcast.
+    pub fn from_signed_broadcast(
+        environment: &Environment<G>,
+        recipient_index: usize,
+        members_pks: &[MemberCommunicationPublicKey<G>],
+        broadcast_messages: &[Option<SignedBroadcast<G, BroadcastPhase2<G>>>],
+    ) -> Vec<Self> {
+        let messages = verified_messages(
+            environment,
+            recipient_index,
+            members_pks,
+            broadcast_messages,
+        );
+        Self::from_broadcast(environment, recipient_index, &messages)
+    }
 }
 
 #[derive(Clone)]
@@ -913,11 +1144,12 @@ pub struct MembersFetchedState3<G: PrimeGroupElement> {
     sender_index: usize,
     /// Party might have not sent the value.
     committed_coefficients: Option<Vec<G>>,
+    proof_of_knowledge: Option<SchnorrZkp<G>>,
 }
 
 impl<G: PrimeGroupElement> MembersFetchedState3<G> {
-    /// The order of broadcast_messages needs to be from low to high index.
-    /// todo: change this requirement.
+    /// The order of broadcast_messages needs to be from low to high index. If messages are
+    /// received in arbitrary order, use a `BroadcastCollector` instead.
     pub fn from_broadcast(
         environment: &Environment<G>,
         recipient_index: usize,
@@ -941,6 +1173,7 @@ impl<G: PrimeGroupElement> MembersFetchedState3<G> {
                     output.push(MembersFetchedState3 {
                         sender_index: index,
                         committed_coefficients: None,
+                        proof_of_knowledge: None,
                     });
                     continue;
                 }
@@ -948,16 +1181,36 @@ impl<G: PrimeGroupElement> MembersFetchedState3<G> {
                 output.push(MembersFetchedState3 {
                     sender_index: index,
                     committed_coefficients: Some(broadcast_message.clone().committed_coefficients),
+                    proof_of_knowledge: Some(broadcast_message.proof_of_knowledge.clone()),
                 });
             } else {
                 output.push(MembersFetchedState3 {
                     sender_index: index,
                     committed_coefficients: None,
+                    proof_of_knowledge: None,
                 })
             }
         }
         output
     }
+
+    /// Same as `from_broadcast`, but takes as input the signed broadcast messages. Messages
+    /// whose signature does not validate under the key of the sender in `members_pks` are
+    /// discarded, as if the sender did not broadcast.
+    pub fn from_signed_broadcast(
+        environment: &Environment<G>,
+        recipient_index: usize,
+        members_pks: &[MemberCommunicationPublicKey<G>],
+        broadcast_messages: &[Option<SignedBroadcast<G, BroadcastPhase3<G>>>],
+    ) -> Vec<Self> {
+        let messages = verified_messages(
+            environment,
+            recipient_index,
+            members_pks,
+            broadcast_messages,
+        );
+        Self::from_broadcast(environment, recipient_index, &messages)
+    }
 }
 
 #[derive(Clone)]
@@ -989,6 +1242,24 @@ impl<G: PrimeGroupElement> MembersFetchedState4<G> {
         }
         output
     }
+
+    /// Same as `from_broadcast`, but takes as input the signed broadcast messages. Messages
+    /// whose signature does not validate under the key of the sender in `members_pks` are
+    /// discarded, as if the sender did not broadcast.
+    pub fn from_signed_broadcast(
+        environment: &Environment<G>,
+        recipient_index: usize,
+        members_pks: &[MemberCommunicationPublicKey<G>],
+        broadcast_messages: &[Option<SignedBroadcast<G, BroadcastPhase4<G>>>],
+    ) -> Vec<Self> {
+        let messages = verified_messages(
+            environment,
+            recipient_index,
+            members_pks,
+            broadcast_messages,
+        );
+        Self::from_broadcast(environment, recipient_index, &messages)
+    }
 }
 
 #[derive(Clone)]
@@ -1020,6 +1291,24 @@ impl<G: PrimeGroupElement> MembersFetchedState5<G> {
         }
         output
     }
+
+    /// Same as `from_broadcast`, but takes as input the signed broadcast messages. Messages
+    /// whose signature does not validate under the key of the sender in `members_pks` are
+    /// discarded, as if the sender did not broadcast.
+    pub fn from_signed_broadcast(
+        environment: &Environment<G>,
+        recipient_index: usize,
+        members_pks: &[MemberCommunicationPublicKey<G>],
+        broadcast_messages: &[Option<SignedBroadcast<G, BroadcastPhase5<G>>>],
+    ) -> Vec<Self> {
+        let messages = verified_messages(
+            environment,
+            recipient_index,
+            members_pks,
+            broadcast_messages,
+        );
+        Self::from_broadcast(environment, recipient_index, &messages)
+    }
 }
 
 #[derive(Clone)]
@@ -1068,6 +1357,8 @@ impl<'a, G: PrimeGroupElement> FetchedMisbehaviourComplaints<'a, G> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dkg::equivocation::{detect_equivocations, EchoBroadcast, SignedDigest};
+    use crate::polynomial::lagrange_interpolation;
 
     use curve25519_dalek::ristretto::RistrettoPoint;
     use rand_core::OsRng;
@@ -1079,16 +1370,17 @@ mod tests {
         let shared_string = b"Example of a shared string.".to_owned();
         let threshold = 0;
         let nr_members = 2;
-        let environment = Environment::init(threshold, nr_members, &shared_string);
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
 
-        let mc1 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc2 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 2);
+        let (mc1, mc2) = (&keys[0], &keys[1]);
         let mc = [mc1.to_public(), mc2.to_public()];
 
         let (m1, _broadcast1) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc1, &mc, 1);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
         let (_m2, broadcast2) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc2, &mc, 2);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
 
         // Now, party one fetches the state of the other party, mainly party two
         let fetched_state =
@@ -1102,6 +1394,25 @@ mod tests {
         assert!(phase_2.0.is_ok());
     }
 
+    #[test]
+    fn redacted_phase_debug() {
+        let mut rng = OsRng;
+
+        let shared_string = b"Example of a shared string.".to_owned();
+        let environment = Environment::init(0, 2, &shared_string, b"Example session");
+
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 2);
+        let (mc1, mc2) = (&keys[0], &keys[1]);
+        let mc = [mc1.to_public(), mc2.to_public()];
+
+        let (m1, _broadcast1) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
+
+        let communication_sk = format!("{:?}", mc1.0.sk);
+        assert!(!format!("{:?}", m1).contains(&communication_sk));
+        assert!(m1.danger_debug().contains(&communication_sk));
+    }
+
     #[test]
     fn invalid_phase_2() {
         let mut rng = OsRng;
@@ -1109,19 +1420,19 @@ mod tests {
         let shared_string = b"Example of a shared string.".to_owned();
         let threshold = 1;
         let nr_members = 3;
-        let environment = Environment::init(threshold, nr_members, &shared_string);
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
 
-        let mc1 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc2 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc3 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
         let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
 
         let (m1, _broad_1) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc1, &mc, 1);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
         let (_m2, mut broad_2) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc2, &mc, 2);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
         let (_m3, mut broad_3) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc3, &mc, 3);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
 
         // Now, party one fetches invalid state of the other parties, mainly party two and three
         broad_2.committed_coefficients = vec![PrimeGroupElement::zero(); threshold + 1];
@@ -1156,6 +1467,107 @@ mod tests {
             .is_ok());
     }
 
+    #[test]
+    fn deterministic_init() {
+        use crate::dkg::broadcast::PhaseBroadcast;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(5);
+
+        let shared_string = b"Example of a shared string.".to_owned();
+        let environment = Environment::init(2, 6, &shared_string, b"Example session");
+
+        let keys: Vec<MemberCommunicationKey<RistrettoPoint>> =
+            MemberCommunicationKey::generate_committee(&mut rng, 6);
+        let mc: Vec<MemberCommunicationPublicKey<RistrettoPoint>> =
+            keys.iter().map(|key| key.to_public()).collect();
+
+        // The broadcast message only depends on the output of the rng, and not on the order in
+        // which the shares are encrypted, so it is the same with and without the `rayon`
+        // feature.
+        let (_, broadcast) = DistributedKeyGeneration::init(
+            &mut StdRng::seed_from_u64(7),
+            &environment,
+            &keys[0],
+            &mc,
+            1,
+        );
+        let digest: String = broadcast
+            .digest()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        assert_eq!(
+            digest,
+            "02c3b0f7e4eb22ba0f7a3c4c31c528d4f55bf173b1d96ec1b72994101f7f9d09\
+             4124f24e895eb3da25d07fc9073dcf46f4ca1352d62b8f9ce747dda5671c7875"
+        );
+    }
+
+    #[test]
+    fn batch_share_verification() {
+        let mut rng = OsRng;
+
+        let shared_string = b"Example of a shared string.".to_owned();
+        let threshold = 2;
+        let nr_members = 5;
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
+
+        let keys: Vec<MemberCommunicationKey<RistrettoPoint>> =
+            MemberCommunicationKey::generate_committee(&mut rng, nr_members);
+        let mc: Vec<MemberCommunicationPublicKey<RistrettoPoint>> =
+            keys.iter().map(|key| key.to_public()).collect();
+
+        let mut members = Vec::new();
+        let mut broadcasts = Vec::new();
+        for (i, key) in keys.iter().enumerate() {
+            let (member, broadcast) =
+                DistributedKeyGeneration::init(&mut rng, &environment, key, &mc, i + 1);
+            members.push(member);
+            broadcasts.push(Some(broadcast));
+        }
+
+        let (phase_2, broadcast_data) = members
+            .remove(0)
+            .proceed_with_broadcast(&broadcasts[1..], &mut rng);
+        assert!(broadcast_data.is_none());
+
+        let mut received_shares: Vec<DecryptedShares<RistrettoPoint>> = phase_2
+            .unwrap()
+            .state
+            .indexed_received_shares
+            .into_iter()
+            .map(|shares| shares.unwrap())
+            .collect();
+        let shares: Vec<&DecryptedShares<RistrettoPoint>> = received_shares.iter().collect();
+        assert!(DecryptedShares::batch_verify(
+            &environment,
+            1,
+            &shares,
+            &mut rng
+        ));
+
+        // A single invalid share makes the batch fail, and is identified individually.
+        received_shares[3].decrypted_share +=
+            <RistrettoPoint as PrimeGroupElement>::CorrespondingScalar::one();
+        let shares: Vec<&DecryptedShares<RistrettoPoint>> = received_shares.iter().collect();
+        assert!(!DecryptedShares::batch_verify(
+            &environment,
+            1,
+            &shares,
+            &mut rng
+        ));
+        let invalid: Vec<usize> = received_shares
+            .iter()
+            .enumerate()
+            .filter(|(_, shares)| !shares.verify(&environment, 1))
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(invalid, [3]);
+    }
+
     #[test]
     fn misbehaving_parties() {
         let mut rng = OsRng;
@@ -1164,19 +1576,19 @@ mod tests {
 
         let threshold = 1;
         let nr_members = 3;
-        let environment = Environment::init(threshold, nr_members, &shared_string);
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
 
-        let mc1 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc2 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc3 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
         let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
 
         let (m1, broad_1) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc1, &mc, 1);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
         let (_m2, broad_2) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc2, &mc, 2);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
         let (_m3, mut broad_3) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc3, &mc, 3);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
 
         let broadcast_data_phase_1 = [
             Some(broad_1.clone()),
@@ -1226,6 +1638,115 @@ mod tests {
         assert_eq!(phase_3.unwrap().state.qualified_set, [1, 1, 0])
     }
 
+    #[test]
+    fn equivocating_party() {
+        let mut rng = OsRng;
+
+        let shared_string = b"Example of a shared string.".to_owned();
+
+        let threshold = 1;
+        let nr_members = 3;
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
+
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
+        let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
+
+        let (m1, broad_1) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
+        let (_m2, broad_2) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
+        let (_m3, broad_3) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
+        // Party 3 sends a different, but valid, message to party 2.
+        let (_m3, other_broad_3) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
+
+        let digest_1 = SignedDigest::sign(&environment, 1, &broad_1, mc1, &mut rng);
+        let digest_2 = SignedDigest::sign(&environment, 2, &broad_2, mc2, &mut rng);
+        let digest_3 = SignedDigest::sign(&environment, 3, &broad_3, mc3, &mut rng);
+        let other_digest_3 = SignedDigest::sign(&environment, 3, &other_broad_3, mc3, &mut rng);
+
+        // Party 1 does not detect any misbehaviour with the data it received.
+        let fetched_state = MembersFetchedState1::from_broadcast(
+            &environment,
+            1,
+            &[Some(broad_2.clone()), Some(broad_3.clone())],
+        );
+        let (phase_2, broadcast_data) = m1.proceed(&fetched_state, &mut rng);
+        assert!(broadcast_data.is_none());
+
+        // But the echo step reveals that party 3 equivocated.
+        let echo_1 = EchoBroadcast::new(
+            1,
+            vec![
+                Some(digest_1.clone()),
+                Some(digest_2.clone()),
+                Some(digest_3),
+            ],
+        );
+        let echo_2 = EchoBroadcast::new(
+            1,
+            vec![Some(digest_1), Some(digest_2), Some(other_digest_3)],
+        );
+        let proofs = detect_equivocations(&environment, &[echo_1, echo_2], &mc);
+
+        let broadcast_data_phase_1 = [Some(broad_1), Some(broad_2), Some(broad_3)];
+        let (phase_3, _broadcast_data_3) =
+            phase_2
+                .unwrap()
+                .proceed_with_equivocations(&[], &broadcast_data_phase_1, &proofs);
+        assert!(phase_3.is_ok());
+        assert_eq!(phase_3.unwrap().state.qualified_set, [1, 1, 0])
+    }
+
+    #[test]
+    fn signed_broadcast_phase_1() {
+        let mut rng = OsRng;
+
+        let shared_string = b"Example of a shared string.".to_owned();
+        let threshold = 1;
+        let nr_members = 3;
+        let environment = Environment::init(
+            threshold,
+            nr_members,
+            &shared_string,
+            b"Signed broadcast session",
+        );
+
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
+        let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
+
+        let (m1, _broad_1) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
+        let (_m2, broad_2) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
+        let (_m3, broad_3) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
+
+        let signed_2 = SignedBroadcast::sign(&environment, 2, broad_2, mc2, &mut rng);
+        // Party 3's message is signed for a different session, and is therefore discarded.
+        let other_environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Other session");
+        let signed_3 = SignedBroadcast::sign(&other_environment, 3, broad_3, mc3, &mut rng);
+        assert!(signed_3.verify(&environment, &mc[2]).is_err());
+
+        let fetched_state = MembersFetchedState1::from_signed_broadcast(
+            &environment,
+            1,
+            &mc,
+            &[Some(signed_2), Some(signed_3)],
+        );
+        assert!(fetched_state[0].committed_coeffs.is_some());
+        assert!(fetched_state[1].committed_coeffs.is_none());
+
+        let (phase_2, _broadcast_data) = m1.proceed(&fetched_state, &mut rng);
+        assert!(phase_2.is_ok());
+        assert_eq!(phase_2.unwrap().state.qualified_set, [1, 1, 0]);
+    }
+
     #[test]
     fn phase_4_tests() {
         let mut rng = OsRng;
@@ -1234,19 +1755,19 @@ mod tests {
 
         let threshold = 1;
         let nr_members = 3;
-        let environment = Environment::init(threshold, nr_members, &shared_string);
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
 
-        let mc1 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc2 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc3 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
         let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
 
         let (m1, broad_1) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc1, &mc, 1);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
         let (m2, broad_2) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc2, &mc, 2);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
         let (_m3, broad_3) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc3, &mc, 3);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
 
         let broadcast_data_phase_1 = [
             Some(broad_1.clone()),
@@ -1275,7 +1796,7 @@ mod tests {
         assert!(party_2_phase_2.is_ok());
 
         // We proceed to phase three
-        let (party_1_phase_3, _party_1_broadcast_data_3) = party_1_phase_2
+        let (party_1_phase_3, party_1_broadcast_data_3) = party_1_phase_2
             .unwrap()
             .proceed(&[], &broadcast_data_phase_1);
         let (party_2_phase_3, party_2_broadcast_data_3) = party_2_phase_2
@@ -1285,24 +1806,45 @@ mod tests {
         assert!(party_1_phase_3.is_ok() && party_2_phase_3.is_ok());
 
         // Fetched state of party 1. We have mimic'ed that party three stopped participating.
+        let party_2_broadcast_data_3 = party_2_broadcast_data_3.unwrap();
         let party_1_fetched_state_phase_3 = vec![MembersFetchedState3 {
             sender_index: 2,
-            committed_coefficients: Some(party_2_broadcast_data_3.unwrap().committed_coefficients),
+            committed_coefficients: Some(party_2_broadcast_data_3.committed_coefficients.clone()),
+            proof_of_knowledge: Some(party_2_broadcast_data_3.proof_of_knowledge.clone()),
         }];
 
         // The protocol should finalise, given that we have two honest parties finalising the protocol
         // which is higher than the threshold
+        let party_1_phase_3 = party_1_phase_3.unwrap();
         assert!(party_1_phase_3
-            .unwrap()
+            .clone()
             .proceed(&party_1_fetched_state_phase_3)
             .0
             .is_ok());
 
+        // The proof of knowledge is bound to the dealer, so party 2 cannot reuse the proof of
+        // party 1, even with valid shares. Party 1 then accuses party 2.
+        let party_1_broadcast_data_3 = party_1_broadcast_data_3.unwrap();
+        assert!(party_2_broadcast_data_3
+            .verify_proof_of_knowledge(&environment, 2)
+            .is_ok());
+        assert!(party_2_broadcast_data_3
+            .verify_proof_of_knowledge(&environment, 1)
+            .is_err());
+        let replayed_proof = vec![MembersFetchedState3 {
+            proof_of_knowledge: Some(party_1_broadcast_data_3.proof_of_knowledge.clone()),
+            ..party_1_fetched_state_phase_3[0].clone()
+        }];
+        let (failing_phase, complaints) = party_1_phase_3.proceed(&replayed_proof);
+        assert!(failing_phase.is_err());
+        assert_eq!(complaints.unwrap().misbehaving_parties[0].accused_index, 2);
+
         // If party three stops participating, and party 1 misbehaves, the protocol fails for party
         // 2, and there should be the proof of misbehaviour of party 1.
         let party_2_fetched_state_phase_3 = vec![MembersFetchedState3::<RistrettoPoint> {
             sender_index: 1,
             committed_coefficients: Some(vec![PrimeGroupElement::generator(); threshold + 1]),
+            proof_of_knowledge: Some(party_1_broadcast_data_3.proof_of_knowledge),
         }];
 
         let failing_phase = party_2_phase_3
@@ -1320,19 +1862,19 @@ mod tests {
 
         let threshold = 1;
         let nr_members = 3;
-        let environment = Environment::init(threshold, nr_members, &shared_string);
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
 
-        let mc1 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc2 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc3 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
         let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
 
         let (m1, broad_1) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc1, &mc, 1);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
         let (m2, broad_2) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc2, &mc, 2);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
         let (m3, broad_3) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc3, &mc, 3);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
 
         // Parties 1, 2, and 3 publish broad_1, broad_2, and broad_3 respectively in the
         // blockchain. All parties fetched the data.
@@ -1522,19 +2064,19 @@ mod tests {
 
         let threshold = 1;
         let nr_members = 3;
-        let environment = Environment::init(threshold, nr_members, &shared_string);
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
 
-        let mc1 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc2 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc3 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
         let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
 
         let (m1, broad_1) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc1, &mc, 1);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
         let (m2, broad_2) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc2, &mc, 2);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
         let (m3, broad_3) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc3, &mc, 3);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
 
         let optional_broadcasts_phase_1 = [
             Some(broad_1.clone()),
@@ -1662,19 +2204,19 @@ mod tests {
 
         let threshold = 1;
         let nr_members = 3;
-        let environment = Environment::init(threshold, nr_members, &shared_string);
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
 
-        let mc1 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc2 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-        let mc3 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
         let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
 
         let (m1, broad_1) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc1, &mc, 1);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
         let (m2, broad_2) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc2, &mc, 2);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
         let (m3, broad_3) =
-            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc3, &mc, 3);
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
 
         let broadcasts_phase_1 = [&broad_1, &broad_2, &broad_3];
 
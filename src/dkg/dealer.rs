@@ -0,0 +1,167 @@
+//! Trusted dealer, which shares a chosen secret among the members of a committee with
+//! Pedersen VSS, e.g. to migrate an existing single-party key to a committee. Unlike the
+//! distributed key generation, the dealer need not be a member of the committee, and the
+//! members do not run the phases of the protocol: the dealer publishes a `DealerBroadcast`,
+//! with the commitments to the coefficients of the sharing and hiding polynomials,
+//! \\( E_l = g^{a_l}h^{b_l} \\), and the shares of every member encrypted under its
+//! communication key. Member \\( i \\) decrypts its shares and verifies them with the same
+//! check as in phase 2 of the distributed key generation,
+//! \\( g^{s_i}h^{s_i'} = \prod_{l=0}^t E_l^{i^l}. \\)
+//!
+//! The commitments are hiding, so the dealing reveals nothing about the secret, not even its
+//! public key.
+use super::broadcast::{DecryptedShares, EncryptedShares};
+use super::committee::Environment;
+use super::procedure_keys::{
+    MemberCommunicationKey, MemberCommunicationPublicKey, MemberSecretShare,
+};
+use crate::cryptography::elgamal::SecretKey;
+use crate::errors::DkgError;
+use crate::traits::{PrimeGroupElement, Scalar};
+use crate::vss::pedersen;
+use rand_core::{CryptoRng, RngCore};
+
+/// Dealing of a trusted dealer, with the commitments to the coefficients of the polynomials,
+/// and the encrypted shares of every member.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DealerBroadcast<G: PrimeGroupElement> {
+    pub committed_coefficients: Vec<G>,
+    pub encrypted_shares: Vec<EncryptedShares<G>>,
+}
+
+impl<G: PrimeGroupElement> DealerBroadcast<G> {
+    /// Share `secret` among the members of the committee, with communication keys
+    /// `committee_pks`, such that any `threshold + 1` of them can reconstruct it. As in the
+    /// distributed key generation, the `i`-th smallest key belongs to member `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of public keys is not the same as `nr_members` in the
+    /// `environment`.
+    pub fn new<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        environment: &Environment<G>,
+        committee_pks: &[MemberCommunicationPublicKey<G>],
+        secret: &G::CorrespondingScalar,
+    ) -> Self {
+        assert_eq!(committee_pks.len(), environment.nr_members);
+        let (dealing, shares) = pedersen::Dealing::new(
+            rng,
+            &environment.commitment_key,
+            environment.threshold,
+            environment.nr_members,
+            secret,
+        );
+
+        let mut ordered_pks = committee_pks.to_vec();
+        ordered_pks.sort();
+
+        let encrypted_shares = shares
+            .iter()
+            .zip(ordered_pks.iter())
+            .map(|(share, pk)| EncryptedShares {
+                recipient_index: share.index,
+                encrypted_share: pk.hybrid_encrypt(&share.value.to_bytes(), rng),
+                encrypted_randomness: pk.hybrid_encrypt(&share.randomness.to_bytes(), rng),
+            })
+            .collect();
+
+        DealerBroadcast {
+            committed_coefficients: dealing.into_commitments(),
+            encrypted_shares,
+        }
+    }
+
+    /// Decrypt the shares of member `index`, with its `communication_key`, and verify them
+    /// against the committed coefficients. Returns the secret share of the member.
+    ///
+    /// # Errors
+    ///
+    /// * `DkgError::FetchedInvalidData` if the dealing has no shares for member `index`.
+    /// * `DkgError::DecodingToScalarFailed` if the decryption of the shares fails.
+    /// * `DkgError::ShareValidityFailed` if the shares are not consistent with the committed
+    ///   coefficients, or the dealer did not commit to exactly `threshold + 1` coefficients.
+    pub fn receive(
+        &self,
+        environment: &Environment<G>,
+        communication_key: &MemberCommunicationKey<G>,
+        index: usize,
+    ) -> Result<MemberSecretShare<G>, DkgError> {
+        let encrypted_shares = index
+            .checked_sub(1)
+            .and_then(|position| self.encrypted_shares.get(position))
+            .filter(|shares| shares.recipient_index == index)
+            .ok_or(DkgError::FetchedInvalidData)?;
+
+        let (share, randomness) = communication_key.decrypt_shares(encrypted_shares);
+        let shares = DecryptedShares {
+            decrypted_share: share.ok_or(DkgError::DecodingToScalarFailed)?,
+            decrypted_randomness: randomness.ok_or(DkgError::DecodingToScalarFailed)?,
+            committed_coefficients: self.committed_coefficients.clone(),
+        };
+        if !shares.verify(environment, index) {
+            return Err(DkgError::ShareValidityFailed);
+        }
+
+        Ok(MemberSecretShare(SecretKey {
+            sk: shares.decrypted_share,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vss;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar as RScalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn trusted_dealer() {
+        let mut rng = OsRng;
+        let environment =
+            Environment::init(1, 3, b"Example of a shared string.", b"Example session");
+        let communication_keys =
+            MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let committee_pks: Vec<_> = communication_keys.iter().map(|k| k.to_public()).collect();
+
+        let secret = RScalar::random(&mut rng);
+        let mut broadcast = DealerBroadcast::new(&mut rng, &environment, &committee_pks, &secret);
+
+        let shares: Vec<vss::Share<RScalar>> = communication_keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| vss::Share {
+                index: i + 1,
+                value: broadcast.receive(&environment, key, i + 1).unwrap().0.sk,
+            })
+            .collect();
+        assert_eq!(vss::reconstruct(1, &shares[1..]), Ok(secret));
+
+        // Shares of a different member, or of a non-member, are rejected.
+        assert!(broadcast
+            .receive(&environment, &communication_keys[0], 2)
+            .is_err());
+        assert_eq!(
+            broadcast.receive(&environment, &communication_keys[0], 4),
+            Err(DkgError::FetchedInvalidData)
+        );
+
+        // Shares inconsistent with the commitments, or commitments to a polynomial of larger
+        // degree, are rejected.
+        broadcast.encrypted_shares.swap(0, 2);
+        broadcast.encrypted_shares[0].recipient_index = 1;
+        assert_eq!(
+            broadcast.receive(&environment, &communication_keys[2], 1),
+            Err(DkgError::ShareValidityFailed)
+        );
+        broadcast
+            .committed_coefficients
+            .push(RistrettoPoint::zero());
+        assert_eq!(
+            broadcast.receive(&environment, &communication_keys[1], 2),
+            Err(DkgError::ShareValidityFailed)
+        );
+    }
+}
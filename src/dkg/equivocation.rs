@@ -0,0 +1,303 @@
+//! Detection of members that broadcast different messages to different participants. The
+//! protocol assumes a consistent broadcast channel, in which all members fetch the same message
+//! from a given sender. If the channel does not provide this guarantee, a dishonest dealer can
+//! send different contents (e.g. different `BroadcastPhase1`) to different members.
+//!
+//! To detect such behaviour, every member signs the digest of its broadcast message,
+//! `SignedDigest`, and publishes it together with the message. After every phase, members
+//! echo the signed digests they received from every sender in an `EchoBroadcast`. Two valid
+//! signed digests of the same sender and phase with different contents form a portable
+//! `EquivocationProof`, which anyone can verify with the sender's communication public key.
+//! Proofs passed to `Phases::<G, Phase2>::proceed_with_equivocations` disqualify the sender,
+//! as this is where the qualified set is computed.
+use crate::cryptography::signature::Signature;
+use crate::dkg::broadcast::PhaseBroadcast;
+use crate::dkg::committee::Environment;
+use crate::dkg::procedure_keys::{MemberCommunicationKey, MemberCommunicationPublicKey};
+use crate::errors::DkgError;
+use crate::traits::PrimeGroupElement;
+use rand_core::{CryptoRng, RngCore};
+
+/// Digest of a member's broadcast message in a given phase, signed by the member.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedDigest<G: PrimeGroupElement> {
+    pub(crate) phase: usize,
+    pub(crate) sender_index: usize,
+    pub(crate) digest: [u8; 64],
+    pub(crate) signature: Signature<G>,
+}
+
+impl<G: PrimeGroupElement> SignedDigest<G> {
+    // Bytes covered by the signature. These bind the digest to the session of the protocol.
+    fn signed_bytes(
+        environment: &Environment<G>,
+        phase: usize,
+        sender_index: usize,
+        digest: &[u8; 64],
+    ) -> Vec<u8> {
+        let mut bytes = b"DKG broadcast digest".to_vec();
+        bytes.extend_from_slice(&(environment.session_id.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&environment.session_id);
+        bytes.extend_from_slice(&(phase as u64).to_le_bytes());
+        bytes.extend_from_slice(&(sender_index as u64).to_le_bytes());
+        bytes.extend_from_slice(digest);
+        bytes
+    }
+
+    /// Sign the digest of `message`, broadcast by the member with index `sender_index` in the
+    /// session defined by `environment`.
+    pub fn sign<T, R>(
+        environment: &Environment<G>,
+        sender_index: usize,
+        message: &T,
+        secret_key: &MemberCommunicationKey<G>,
+        rng: &mut R,
+    ) -> Self
+    where
+        T: PhaseBroadcast,
+        R: CryptoRng + RngCore,
+    {
+        let digest = message.digest();
+        let signature = secret_key.sign(
+            &Self::signed_bytes(environment, T::PHASE, sender_index, &digest),
+            rng,
+        );
+
+        Self {
+            phase: T::PHASE,
+            sender_index,
+            digest,
+            signature,
+        }
+    }
+
+    /// Index of the member that signed the digest.
+    pub fn sender_index(&self) -> usize {
+        self.sender_index
+    }
+
+    /// Phase of the signed message.
+    pub fn phase(&self) -> usize {
+        self.phase
+    }
+
+    /// Returns whether `message` is the message whose digest was signed.
+    pub fn matches<T: PhaseBroadcast>(&self, message: &T) -> bool {
+        self.phase == T::PHASE && self.digest == message.digest()
+    }
+
+    /// Verify the signature of the digest with the communication public key of the sender, for
+    /// the session defined by `environment`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidSignature` if the signature does not validate.
+    pub fn verify(
+        &self,
+        environment: &Environment<G>,
+        sender_pk: &MemberCommunicationPublicKey<G>,
+    ) -> Result<(), DkgError> {
+        let signed_bytes =
+            Self::signed_bytes(environment, self.phase, self.sender_index, &self.digest);
+        if sender_pk.verify_signature(&signed_bytes, &self.signature) {
+            Ok(())
+        } else {
+            Err(DkgError::InvalidSignature)
+        }
+    }
+}
+
+/// View of a member of all broadcast messages of a phase. `views[j]` contains the signed digest
+/// the member received from member `j + 1`, or `None` if it received nothing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EchoBroadcast<G: PrimeGroupElement> {
+    pub(crate) phase: usize,
+    pub(crate) views: Vec<Option<SignedDigest<G>>>,
+}
+
+impl<G: PrimeGroupElement> EchoBroadcast<G> {
+    /// Build the echo message of `phase` with the signed digests received from every member,
+    /// ordered by sender index.
+    pub fn new(phase: usize, views: Vec<Option<SignedDigest<G>>>) -> Self {
+        Self { phase, views }
+    }
+}
+
+/// Portable proof that a member signed two different messages in the same phase.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EquivocationProof<G: PrimeGroupElement> {
+    first: SignedDigest<G>,
+    second: SignedDigest<G>,
+}
+
+impl<G: PrimeGroupElement> EquivocationProof<G> {
+    /// Build a proof of equivocation from two signed digests. Returns `None` if the digests
+    /// do not conflict, i.e. if they belong to different senders or phases, or if they sign
+    /// the same message.
+    pub fn new(first: SignedDigest<G>, second: SignedDigest<G>) -> Option<Self> {
+        if first.sender_index != second.sender_index
+            || first.phase != second.phase
+            || first.digest == second.digest
+        {
+            return None;
+        }
+        Some(Self { first, second })
+    }
+
+    /// Index of the member that equivocated.
+    pub fn accused_index(&self) -> usize {
+        self.first.sender_index
+    }
+
+    /// Phase in which the member equivocated.
+    pub fn phase(&self) -> usize {
+        self.first.phase
+    }
+
+    /// Verify the proof of equivocation, given the `environment` of the session and the
+    /// ordered communication public keys of the committee, `members_pks`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidProofOfMisbehaviour` if the two signed digests do not conflict or if
+    /// the accused index is not part of the committee, and `InvalidSignature` if any of the
+    /// signatures does not validate under the key of the accused member.
+    pub fn verify(
+        &self,
+        environment: &Environment<G>,
+        members_pks: &[MemberCommunicationPublicKey<G>],
+    ) -> Result<(), DkgError> {
+        if self.first.sender_index != self.second.sender_index
+            || self.first.phase != self.second.phase
+            || self.first.digest == self.second.digest
+            || self.accused_index() == 0
+            || self.accused_index() > members_pks.len()
+        {
+            return Err(DkgError::InvalidProofOfMisbehaviour);
+        }
+
+        let accused_pk = &members_pks[self.accused_index() - 1];
+        self.first.verify(environment, accused_pk)?;
+        self.second.verify(environment, accused_pk)
+    }
+}
+
+/// Compare the echoed views of all members, and return a proof of equivocation for every sender
+/// for which two valid, conflicting, signed digests are found. Signed digests that do not
+/// validate under the key of their sender are ignored.
+pub fn detect_equivocations<G: PrimeGroupElement>(
+    environment: &Environment<G>,
+    echoes: &[EchoBroadcast<G>],
+    members_pks: &[MemberCommunicationPublicKey<G>],
+) -> Vec<EquivocationProof<G>> {
+    let mut proofs: Vec<EquivocationProof<G>> = Vec::new();
+    // First valid signed digest seen for every phase and sender.
+    let mut seen: Vec<SignedDigest<G>> = Vec::new();
+
+    for echo in echoes {
+        for (index, view) in echo.views.iter().enumerate() {
+            let signed_digest = match view {
+                Some(signed_digest) => signed_digest,
+                None => continue,
+            };
+            let sender_index = index + 1;
+            if signed_digest.sender_index != sender_index
+                || signed_digest.phase != echo.phase
+                || sender_index > members_pks.len()
+                || proofs
+                    .iter()
+                    .any(|p| p.phase() == echo.phase && p.accused_index() == sender_index)
+                || signed_digest
+                    .verify(environment, &members_pks[sender_index - 1])
+                    .is_err()
+            {
+                continue;
+            }
+
+            match seen
+                .iter()
+                .position(|d| d.phase == echo.phase && d.sender_index == sender_index)
+            {
+                Some(position) => {
+                    if let Some(proof) =
+                        EquivocationProof::new(seen[position].clone(), signed_digest.clone())
+                    {
+                        proofs.push(proof);
+                    }
+                }
+                None => seen.push(signed_digest.clone()),
+            }
+        }
+    }
+    proofs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dkg::committee::DistributedKeyGeneration;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use rand_core::OsRng;
+
+    #[test]
+    fn detect_and_verify() {
+        let mut rng = OsRng;
+
+        let shared_string = b"Example of a shared string.".to_owned();
+        let threshold = 1;
+        let nr_members = 3;
+        let environment =
+            Environment::init(threshold, nr_members, &shared_string, b"Example session");
+
+        let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+        let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
+        let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
+
+        let (_m1, broad_1) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
+        let (_m2, broad_2) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
+        let (_m3, broad_3) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
+        // Party 3 sends a different message to party 2.
+        let (_m3, other_broad_3) =
+            DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
+
+        let digest_1 = SignedDigest::sign(&environment, 1, &broad_1, mc1, &mut rng);
+        let digest_2 = SignedDigest::sign(&environment, 2, &broad_2, mc2, &mut rng);
+        let digest_3 = SignedDigest::sign(&environment, 3, &broad_3, mc3, &mut rng);
+        let other_digest_3 = SignedDigest::sign(&environment, 3, &other_broad_3, mc3, &mut rng);
+
+        assert!(digest_3.matches(&broad_3));
+        assert!(!digest_3.matches(&other_broad_3));
+
+        let echo_1 = EchoBroadcast::new(
+            1,
+            vec![
+                Some(digest_1.clone()),
+                Some(digest_2.clone()),
+                Some(digest_3),
+            ],
+        );
+        let echo_2 = EchoBroadcast::new(
+            1,
+            vec![Some(digest_1), Some(digest_2), Some(other_digest_3)],
+        );
+
+        let proofs = detect_equivocations(&environment, &[echo_1.clone(), echo_2], &mc);
+        assert_eq!(proofs.len(), 1);
+        assert_eq!(proofs[0].accused_index(), 3);
+        assert_eq!(proofs[0].phase(), 1);
+        assert!(proofs[0].verify(&environment, &mc).is_ok());
+
+        // The proof does not validate against a different committee key.
+        let wrong_pks = [mc1.to_public(), mc2.to_public(), mc1.to_public()];
+        assert_eq!(
+            proofs[0].verify(&environment, &wrong_pks),
+            Err(DkgError::InvalidSignature)
+        );
+
+        // Consistent views do not produce proofs.
+        assert!(detect_equivocations(&environment, &[echo_1.clone(), echo_1], &mc).is_empty());
+    }
+}
@@ -1,3 +1,7 @@
 pub mod broadcast;
+pub mod collector;
 pub mod committee;
+pub mod dealer;
+pub mod equivocation;
 pub mod procedure_keys;
+pub mod threshold_decryption;
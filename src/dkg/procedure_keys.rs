@@ -1,7 +1,14 @@
 /// todo: eventually https://internals.rust-lang.org/t/pre-rfc-module-level-generics/12015
-use crate::cryptography::elgamal::{HybridCiphertext, PublicKey, SecretKey};
+use crate::cryptography::ciphertext_vector::CiphertextVector;
+use crate::cryptography::commitment::{CommitmentKey, VectorCommitmentKey};
+use crate::cryptography::elgamal::{Ciphertext, HybridCiphertext, PublicKey, SecretKey};
+use crate::cryptography::shuffle::{self, ShuffleZkp};
+use crate::cryptography::signature::Signature;
+use crate::cryptography::unit_vector::UnitVectorZkp;
 use crate::dkg::committee::EncryptedShares;
+use crate::errors::{DkgError, ProofError};
 use crate::traits::{PrimeGroupElement, Scalar};
+use crate::vss;
 use rand_core::{CryptoRng, RngCore};
 use std::cmp::Ordering;
 
@@ -9,6 +16,13 @@ use std::cmp::Ordering;
 #[derive(Clone, Debug, PartialEq)]
 pub struct MemberSecretShare<G: PrimeGroupElement>(pub(crate) SecretKey<G>);
 
+#[cfg(feature = "zeroize")]
+impl<G: PrimeGroupElement> zeroize::Zeroize for MemberSecretShare<G> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 /// Committee member public key share.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct MemberPublicShare<G: PrimeGroupElement>(pub(crate) PublicKey<G>);
@@ -18,6 +32,13 @@ pub struct MemberPublicShare<G: PrimeGroupElement>(pub(crate) PublicKey<G>);
 #[derive(Clone, Debug, PartialEq)]
 pub struct MemberCommunicationKey<G: PrimeGroupElement>(pub(crate) SecretKey<G>);
 
+#[cfg(feature = "zeroize")]
+impl<G: PrimeGroupElement> zeroize::Zeroize for MemberCommunicationKey<G> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 /// Committee Member communication public key. This differs from the public share, as the members
 /// need a pre-existing keypair to communicate with other members.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -50,9 +71,15 @@ impl<G: PrimeGroupElement> PartialOrd for MemberCommunicationPublicKey<G> {
 pub struct MasterPublicKey<G: PrimeGroupElement>(pub(crate) PublicKey<G>);
 
 impl<G: PrimeGroupElement> MemberSecretShare<G> {
+    /// Debug representation of the share which, unlike `Debug`, includes the secret scalar.
+    /// Handle the output with care, as it discloses the share.
+    pub fn danger_debug(&self) -> String {
+        format!("MemberSecretShare({})", self.0.danger_debug())
+    }
+
     pub fn to_public(&self) -> MemberPublicShare<G> {
         MemberPublicShare(PublicKey {
-            pk: G::generator() * self.0.sk,
+            pk: G::generator_mul(&self.0.sk),
         })
     }
 }
@@ -70,14 +97,29 @@ impl<G: PrimeGroupElement> From<PublicKey<G>> for MemberPublicShare<G> {
 }
 
 impl<G: PrimeGroupElement> MemberCommunicationKey<G> {
+    /// Debug representation of the key which, unlike `Debug`, includes the secret scalar.
+    /// Handle the output with care, as it discloses the key.
+    pub fn danger_debug(&self) -> String {
+        format!("MemberCommunicationKey({})", self.0.danger_debug())
+    }
+
     pub fn new<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
         let sk = SecretKey::generate(rng);
         MemberCommunicationKey(sk)
     }
 
+    /// Generate the communication keys of a committee of `nr_members`, sorted by their public
+    /// keys. As `Phases::init` indexes the members in this order, the key at position `i - 1`
+    /// belongs to member `i`, which is convenient to simulate a committee.
+    pub fn generate_committee<R: RngCore + CryptoRng>(rng: &mut R, nr_members: usize) -> Vec<Self> {
+        let mut keys: Vec<Self> = (0..nr_members).map(|_| Self::new(rng)).collect();
+        keys.sort_by_cached_key(|key| key.to_public());
+        keys
+    }
+
     pub fn to_public(&self) -> MemberCommunicationPublicKey<G> {
         MemberCommunicationPublicKey(PublicKey {
-            pk: G::generator() * self.0.sk,
+            pk: G::generator_mul(&self.0.sk),
         })
     }
 
@@ -85,9 +127,14 @@ impl<G: PrimeGroupElement> MemberCommunicationKey<G> {
         self.0.hybrid_decrypt(ciphertext)
     }
 
+    /// Sign `message` with the communication key.
+    pub fn sign<R: RngCore + CryptoRng>(&self, message: &[u8], rng: &mut R) -> Signature<G> {
+        Signature::sign(&self.0, message, rng)
+    }
+
     pub(crate) fn decrypt_shares(
         &self,
-        shares: EncryptedShares<G>,
+        shares: &EncryptedShares<G>,
     ) -> (
         Option<G::CorrespondingScalar>,
         Option<G::CorrespondingScalar>,
@@ -116,6 +163,19 @@ impl<G: PrimeGroupElement> MemberCommunicationPublicKey<G> {
     {
         self.0.hybrid_encrypt(message, rng)
     }
+
+    pub(crate) fn hybrid_encrypt_with_r(
+        &self,
+        message: &[u8],
+        encryption_randomness: &G::CorrespondingScalar,
+    ) -> HybridCiphertext<G> {
+        self.0.hybrid_encrypt_with_r(message, encryption_randomness)
+    }
+
+    /// Verify that `signature` is a valid signature of `message` under the communication key.
+    pub fn verify_signature(&self, message: &[u8], signature: &Signature<G>) -> bool {
+        signature.verify(&self.0, message)
+    }
 }
 
 impl<G: PrimeGroupElement> MasterPublicKey<G> {
@@ -132,6 +192,127 @@ impl<G: PrimeGroupElement> MasterPublicKey<G> {
     pub fn as_raw(&self) -> &PublicKey<G> {
         &self.0
     }
+
+    /// Encrypt every message of `messages` under the master public key, with lifted ElGamal.
+    pub fn encrypt_vector<R: RngCore + CryptoRng>(
+        &self,
+        messages: &[G::CorrespondingScalar],
+        rng: &mut R,
+    ) -> CiphertextVector<G> {
+        CiphertextVector::encrypt(&self.0, messages, rng)
+    }
+
+    /// Encrypt the unit vector of size `size` with a one at position `choice` under the
+    /// master public key, e.g. a vote for option `choice`.
+    pub fn encrypt_unit_vector<R: RngCore + CryptoRng>(
+        &self,
+        choice: usize,
+        size: usize,
+        rng: &mut R,
+    ) -> CiphertextVector<G> {
+        CiphertextVector::encrypt_unit_vector(&self.0, choice, size, rng)
+    }
+
+    /// Encrypt a ballot for option `choice` out of `size` options under the master public
+    /// key, as a unit vector, together with the proof of its validity.
+    pub fn encrypt_ballot<R: RngCore + CryptoRng>(
+        &self,
+        commitment_key: &CommitmentKey<G>,
+        choice: usize,
+        size: usize,
+        rng: &mut R,
+    ) -> (CiphertextVector<G>, UnitVectorZkp<G>) {
+        CiphertextVector::encrypt_unit_vector_with_proof(&self.0, commitment_key, choice, size, rng)
+    }
+
+    /// Verify that `ballot` encrypts a unit vector under the master public key.
+    pub fn verify_ballot(
+        &self,
+        commitment_key: &CommitmentKey<G>,
+        ballot: &CiphertextVector<G>,
+        proof: &UnitVectorZkp<G>,
+    ) -> Result<(), ProofError> {
+        ballot.verify_unit_vector(&self.0, commitment_key, proof)
+    }
+
+    /// Shuffle and re-randomise `ciphertexts`, encrypted under the master public key, e.g.
+    /// to anonymise them before threshold decryption. Returns the shuffled ciphertexts, with
+    /// a proof of the shuffle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ciphertexts` is empty, or if it is longer than `commitment_key`.
+    pub fn shuffle<R: RngCore + CryptoRng>(
+        &self,
+        commitment_key: &VectorCommitmentKey<G>,
+        ciphertexts: &[Ciphertext<G>],
+        rng: &mut R,
+    ) -> (Vec<Ciphertext<G>>, ShuffleZkp<G>) {
+        shuffle::shuffle(&self.0, commitment_key, ciphertexts, rng)
+    }
+
+    /// Verify that `outputs` is a shuffle of `inputs`, encrypted under the master public key.
+    pub fn verify_shuffle(
+        &self,
+        commitment_key: &VectorCommitmentKey<G>,
+        inputs: &[Ciphertext<G>],
+        outputs: &[Ciphertext<G>],
+        proof: &ShuffleZkp<G>,
+    ) -> Result<(), ProofError> {
+        proof.verify(&self.0, commitment_key, inputs, outputs)
+    }
+}
+
+/// Reconstruct the master secret key from the secret shares of at least `threshold + 1`
+/// members, where `indexed_shares` contains pairs of the member index and its share. Every
+/// share is first verified against the public share of its member, `public_shares[i - 1]`
+/// for member `i`, and the reconstructed key against the `master_public_key`.
+///
+/// # Errors
+///
+/// * `DkgError::InvalidShareIndex` if an index is zero, larger than the number of public
+///   shares, or repeated.
+/// * `DkgError::InvalidSecretShares` with the indices of all the members whose share is not
+///   consistent with their public share.
+/// * `DkgError::InsufficientShares` if there are less than `threshold + 1` shares.
+/// * `DkgError::InconsistentMasterKey` if the reconstructed key does not correspond to the
+///   `master_public_key`.
+pub fn reconstruct_secret<G: PrimeGroupElement>(
+    threshold: usize,
+    indexed_shares: &[(usize, MemberSecretShare<G>)],
+    public_shares: &[MemberPublicShare<G>],
+    master_public_key: &MasterPublicKey<G>,
+) -> Result<SecretKey<G>, DkgError> {
+    if let Some(&(index, _)) = indexed_shares
+        .iter()
+        .find(|(index, _)| *index > public_shares.len())
+    {
+        return Err(DkgError::InvalidShareIndex(index));
+    }
+    vss::share_indices::<G::CorrespondingScalar>(indexed_shares.iter().map(|(index, _)| *index))?;
+
+    let faulty_indices: Vec<usize> = indexed_shares
+        .iter()
+        .filter(|(index, share)| share.to_public() != public_shares[index - 1])
+        .map(|(index, _)| *index)
+        .collect();
+    if !faulty_indices.is_empty() {
+        return Err(DkgError::InvalidSecretShares(faulty_indices));
+    }
+
+    let shares: Vec<vss::Share<G::CorrespondingScalar>> = indexed_shares
+        .iter()
+        .map(|(index, share)| vss::Share {
+            index: *index,
+            value: share.0.sk,
+        })
+        .collect();
+    let secret = vss::reconstruct(threshold, &shares)?;
+
+    if G::generator_mul(&secret) != master_public_key.0.pk {
+        return Err(DkgError::InconsistentMasterKey);
+    }
+    Ok(SecretKey { sk: secret })
 }
 
 #[cfg(test)]
@@ -139,6 +320,7 @@ mod tests {
     use super::*;
     use crate::cryptography::elgamal::Keypair;
     use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar;
     use rand_core::OsRng;
 
     #[test]
@@ -152,4 +334,64 @@ mod tests {
 
         assert_eq!(pk_comm, pk_comm_exp);
     }
+
+    #[test]
+    fn secret_reconstruction() {
+        let mut rng = OsRng;
+        let secret = Scalar::random(&mut rng);
+        let (_, shares) = vss::feldman::Dealing::<RistrettoPoint>::new(&mut rng, 2, 5, &secret);
+
+        let secret_shares: Vec<MemberSecretShare<RistrettoPoint>> = shares
+            .iter()
+            .map(|share| MemberSecretShare(SecretKey { sk: share.value }))
+            .collect();
+        let public_shares: Vec<MemberPublicShare<RistrettoPoint>> = secret_shares
+            .iter()
+            .map(|share| share.to_public())
+            .collect();
+        let master_public_key = MasterPublicKey(PublicKey {
+            pk: RistrettoPoint::generator() * secret,
+        });
+
+        let mut indexed_shares: Vec<(usize, MemberSecretShare<RistrettoPoint>)> = [5, 2, 3]
+            .iter()
+            .map(|&index| (index, secret_shares[index - 1].clone()))
+            .collect();
+        let master_secret_key =
+            reconstruct_secret(2, &indexed_shares, &public_shares, &master_public_key)
+                .expect("valid shares reconstruct the secret");
+        assert_eq!(master_secret_key.sk, secret);
+
+        // A share given with the index of another member, and a modified share.
+        indexed_shares[0].0 = 1;
+        indexed_shares[2].1 = MemberSecretShare(SecretKey {
+            sk: shares[2].value + Scalar::one(),
+        });
+        assert_eq!(
+            reconstruct_secret(2, &indexed_shares, &public_shares, &master_public_key).unwrap_err(),
+            DkgError::InvalidSecretShares(vec![1, 3])
+        );
+
+        indexed_shares[0].0 = 6;
+        assert_eq!(
+            reconstruct_secret(2, &indexed_shares, &public_shares, &master_public_key).unwrap_err(),
+            DkgError::InvalidShareIndex(6)
+        );
+        assert_eq!(
+            reconstruct_secret(2, &indexed_shares[1..2], &public_shares, &master_public_key)
+                .unwrap_err(),
+            DkgError::InsufficientShares(3)
+        );
+
+        // Valid shares of a different master key.
+        let other_key = MasterPublicKey(PublicKey {
+            pk: RistrettoPoint::generator(),
+        });
+        indexed_shares[0] = (1, secret_shares[0].clone());
+        indexed_shares[2].1 = secret_shares[2].clone();
+        assert_eq!(
+            reconstruct_secret(2, &indexed_shares, &public_shares, &other_key).unwrap_err(),
+            DkgError::InconsistentMasterKey
+        );
+    }
 }
@@ -0,0 +1,320 @@
+//! Threshold decryption of ciphertext vectors encrypted under the master public key. Every
+//! member \\( i \\) publishes a decryption share of each ciphertext \\( (e_1, e_2) \\),
+//! \\( d_i = e_1^{x_i} \\), where \\( x_i \\) is its secret share, together with a DLEQ proof
+//! that \\( \log_g(X_i) = \log_{e_1}(d_i) \\) for its public share \\( X_i = g^{x_i} \\). Any
+//! `threshold + 1` valid decryption shares combine into
+//! \\( e_1^{x} = \prod_i d_i^{\lambda_i} \\), with \\( \lambda_i \\) the lagrange coefficients
+//! at zero, which decrypts the ciphertext as \\( e_2 - e_1^{x} \\).
+//!
+//! The proofs are batchable, so that the proofs of all the decryption shares of a tally are
+//! verified with a single multiscalar multiplication.
+use crate::cryptography::ciphertext_vector::CiphertextVector;
+use crate::cryptography::discrete_log::BabyStepGiantStep;
+use crate::cryptography::dl_equality::{BatchableDleqZkp, DleqStatement};
+use crate::dkg::procedure_keys::{MemberPublicShare, MemberSecretShare};
+use crate::errors::DkgError;
+use crate::polynomial::LagrangeBasis;
+use crate::traits::{PrimeGroupElement, Scalar};
+use crate::vss;
+use rand_core::{CryptoRng, RngCore};
+
+/// Decryption shares of member `index` for every ciphertext of a `CiphertextVector`, with the
+/// proofs of their correctness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecryptionShare<G: PrimeGroupElement> {
+    index: usize,
+    elements: Vec<G>,
+    proofs: Vec<BatchableDleqZkp<G>>,
+}
+
+impl<G: PrimeGroupElement> DecryptionShare<G> {
+    /// Compute the decryption shares of member `index`, with secret share `secret_share`,
+    /// for every ciphertext of `ciphertexts`.
+    pub fn generate<R>(
+        index: usize,
+        secret_share: &MemberSecretShare<G>,
+        ciphertexts: &CiphertextVector<G>,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let public_share = G::generator_mul(&secret_share.0.sk);
+        let mut elements = Vec::with_capacity(ciphertexts.len());
+        let mut proofs = Vec::with_capacity(ciphertexts.len());
+        for ciphertext in ciphertexts.iter() {
+            let (e1, _) = ciphertext.elements();
+            let element = *e1 * secret_share.0.sk;
+            let statement = DleqStatement::new(G::generator(), *e1, public_share, element);
+            proofs.push(BatchableDleqZkp::generate(
+                &statement,
+                &secret_share.0.sk,
+                rng,
+            ));
+            elements.push(element);
+        }
+
+        DecryptionShare {
+            index,
+            elements,
+            proofs,
+        }
+    }
+
+    /// Index of the member who generated the decryption share.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Verify the decryption shares of `ciphertexts` against the `public_share` of the
+    /// member.
+    pub fn verify(
+        &self,
+        public_share: &MemberPublicShare<G>,
+        ciphertexts: &CiphertextVector<G>,
+    ) -> bool {
+        match self.statements(public_share, ciphertexts) {
+            Some(statements) => statements
+                .iter()
+                .zip(self.proofs.iter())
+                .all(|(statement, proof)| proof.verify(statement).is_ok()),
+            None => false,
+        }
+    }
+
+    // Statements proven by the decryption shares, or `None` if there is not a decryption
+    // share and a proof for every ciphertext.
+    fn statements(
+        &self,
+        public_share: &MemberPublicShare<G>,
+        ciphertexts: &CiphertextVector<G>,
+    ) -> Option<Vec<DleqStatement<G>>> {
+        if self.elements.len() != ciphertexts.len() || self.proofs.len() != ciphertexts.len() {
+            return None;
+        }
+        Some(
+            ciphertexts
+                .iter()
+                .zip(self.elements.iter())
+                .map(|(ciphertext, element)| {
+                    DleqStatement::new(
+                        G::generator(),
+                        *ciphertext.elements().0,
+                        public_share.0.pk,
+                        *element,
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Decrypt `ciphertexts`, encrypted under the master public key, with the decryption shares
+/// of at least `threshold + 1` members, where `public_shares[i - 1]` is the public share of
+/// member `i`. The plaintexts are recovered with the discrete logarithm `table`, so they must
+/// be in its range, as is the case of the tally of a bounded number of votes. The proofs of
+/// all the decryption shares are verified in a single batch, and only if the batch fails, the
+/// decryption shares are verified one by one to find the invalid ones.
+///
+/// # Errors
+///
+/// * `DkgError::InvalidShareIndex` if an index is zero, larger than the number of public
+///   shares, or repeated.
+/// * `DkgError::InvalidDecryptionShares` with the indices of all the members whose decryption
+///   shares do not validate.
+/// * `DkgError::InsufficientShares` if there are less than `threshold + 1` decryption shares.
+/// * `DkgError::DecryptionOutOfBounds` if a plaintext is out of the range of the `table`.
+pub fn decrypt_tally<G, R>(
+    threshold: usize,
+    ciphertexts: &CiphertextVector<G>,
+    decryption_shares: &[DecryptionShare<G>],
+    public_shares: &[MemberPublicShare<G>],
+    table: &BabyStepGiantStep<G>,
+    rng: &mut R,
+) -> Result<Vec<u64>, DkgError>
+where
+    G: PrimeGroupElement,
+    R: CryptoRng + RngCore,
+{
+    if let Some(share) = decryption_shares
+        .iter()
+        .find(|share| share.index > public_shares.len())
+    {
+        return Err(DkgError::InvalidShareIndex(share.index));
+    }
+    vss::share_indices::<G::CorrespondingScalar>(decryption_shares.iter().map(|s| s.index))?;
+
+    let mut statements = Vec::with_capacity(decryption_shares.len() * ciphertexts.len());
+    let mut proofs = Vec::with_capacity(decryption_shares.len() * ciphertexts.len());
+    let mut well_formed = true;
+    for share in decryption_shares {
+        match share.statements(&public_shares[share.index - 1], ciphertexts) {
+            Some(share_statements) => {
+                statements.extend(share_statements);
+                proofs.extend_from_slice(&share.proofs);
+            }
+            None => well_formed = false,
+        }
+    }
+    if !well_formed || BatchableDleqZkp::batch_verify(&statements, &proofs, rng).is_err() {
+        let faulty_indices: Vec<usize> = decryption_shares
+            .iter()
+            .filter(|share| !share.verify(&public_shares[share.index - 1], ciphertexts))
+            .map(|share| share.index)
+            .collect();
+        if !faulty_indices.is_empty() {
+            return Err(DkgError::InvalidDecryptionShares(faulty_indices));
+        }
+    }
+
+    let decryption_shares = decryption_shares
+        .get(..threshold + 1)
+        .ok_or(DkgError::InsufficientShares(threshold + 1))?;
+    let indices =
+        vss::share_indices::<G::CorrespondingScalar>(decryption_shares.iter().map(|s| s.index))?;
+    let basis = LagrangeBasis::new(G::CorrespondingScalar::zero(), &indices);
+
+    ciphertexts
+        .iter()
+        .enumerate()
+        .map(|(j, ciphertext)| {
+            let shared_key = G::vartime_multiscalar_multiplication(
+                basis.coefficients().iter().copied(),
+                decryption_shares.iter().map(|share| share.elements[j]),
+            );
+            let (_, e2) = ciphertext.elements();
+            table
+                .solve(&(*e2 - shared_key))
+                .ok_or(DkgError::DecryptionOutOfBounds)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptography::commitment::{CommitmentKey, VectorCommitmentKey};
+    use crate::cryptography::elgamal::{PublicKey, SecretKey};
+    use crate::dkg::procedure_keys::MasterPublicKey;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn threshold_tally() {
+        let mut rng = OsRng;
+        let master_secret = Scalar::random(&mut rng);
+        let (_, shares) =
+            vss::feldman::Dealing::<RistrettoPoint>::new(&mut rng, 2, 5, &master_secret);
+        let secret_shares: Vec<MemberSecretShare<RistrettoPoint>> = shares
+            .iter()
+            .map(|share| MemberSecretShare(SecretKey { sk: share.value }))
+            .collect();
+        let public_shares: Vec<MemberPublicShare<RistrettoPoint>> = secret_shares
+            .iter()
+            .map(|share| share.to_public())
+            .collect();
+        let master_public_key = MasterPublicKey(PublicKey {
+            pk: RistrettoPoint::generator() * master_secret,
+        });
+
+        let commitment_key = CommitmentKey::generate(&[0u8]);
+        let votes = [1usize, 0, 3, 1, 1, 3, 1];
+        let tally = votes
+            .iter()
+            .fold(CiphertextVector::zero(4), |tally, &vote| {
+                let (ballot, proof) =
+                    master_public_key.encrypt_ballot(&commitment_key, vote, 4, &mut rng);
+                assert!(master_public_key
+                    .verify_ballot(&commitment_key, &ballot, &proof)
+                    .is_ok());
+                tally + ballot
+            });
+
+        let mut decryption_shares: Vec<DecryptionShare<RistrettoPoint>> = [2usize, 5, 4]
+            .iter()
+            .map(|&index| {
+                DecryptionShare::generate(index, &secret_shares[index - 1], &tally, &mut rng)
+            })
+            .collect();
+        let table = BabyStepGiantStep::new(votes.len() as u64);
+        assert_eq!(
+            decrypt_tally(
+                2,
+                &tally,
+                &decryption_shares,
+                &public_shares,
+                &table,
+                &mut rng
+            ),
+            Ok(vec![1, 4, 0, 2])
+        );
+
+        // Shuffled ciphertexts decrypt to a permutation of the tally.
+        let shuffle_key = VectorCommitmentKey::generate(&[0u8], tally.len());
+        let (shuffled, proof) = master_public_key.shuffle(&shuffle_key, tally.as_slice(), &mut rng);
+        assert!(master_public_key
+            .verify_shuffle(&shuffle_key, tally.as_slice(), &shuffled, &proof)
+            .is_ok());
+        let shuffled = CiphertextVector::from_ciphertexts(shuffled);
+        let shuffled_shares: Vec<DecryptionShare<RistrettoPoint>> = [1usize, 3, 4]
+            .iter()
+            .map(|&index| {
+                DecryptionShare::generate(index, &secret_shares[index - 1], &shuffled, &mut rng)
+            })
+            .collect();
+        let mut shuffled_tally = decrypt_tally(
+            2,
+            &shuffled,
+            &shuffled_shares,
+            &public_shares,
+            &table,
+            &mut rng,
+        )
+        .unwrap();
+        shuffled_tally.sort_unstable();
+        assert_eq!(shuffled_tally, vec![0, 1, 2, 4]);
+
+        assert_eq!(
+            decrypt_tally(
+                2,
+                &tally,
+                &decryption_shares[1..],
+                &public_shares,
+                &table,
+                &mut rng
+            ),
+            Err(DkgError::InsufficientShares(3))
+        );
+
+        // Decryption shares computed with a different secret share do not validate.
+        decryption_shares[1] = DecryptionShare::generate(5, &secret_shares[0], &tally, &mut rng);
+        assert!(!decryption_shares[1].verify(&public_shares[4], &tally));
+        assert_eq!(
+            decrypt_tally(
+                2,
+                &tally,
+                &decryption_shares,
+                &public_shares,
+                &table,
+                &mut rng
+            ),
+            Err(DkgError::InvalidDecryptionShares(vec![5]))
+        );
+
+        // So do decryption shares missing the share of a ciphertext.
+        decryption_shares[1] = DecryptionShare::generate(5, &secret_shares[4], &tally, &mut rng);
+        decryption_shares[2].elements.pop();
+        assert_eq!(
+            decrypt_tally(
+                2,
+                &tally,
+                &decryption_shares,
+                &public_shares,
+                &table,
+                &mut rng
+            ),
+            Err(DkgError::InvalidDecryptionShares(vec![4]))
+        );
+    }
+}
@@ -65,6 +65,47 @@ pub enum DkgError {
         error("User included in the qualified set should be dismissed")
     )]
     PartyShouldBeDisqualified,
+    /// This error occurs when a broadcast message is attributed to an index which does not
+    /// correspond to any member of the committee
+    #[cfg_attr(feature = "std", error("Invalid sender index {}"))]
+    InvalidSenderIndex(usize),
+    /// This error occurs when the same broadcast message of a member is received more than once
+    #[cfg_attr(feature = "std", error("Duplicate broadcast from member {}"))]
+    DuplicateBroadcast(usize),
+    /// This error occurs when a member broadcasts two different messages in the same phase
+    #[cfg_attr(feature = "std", error("Member {} broadcast conflicting messages"))]
+    EquivocatingBroadcast(usize),
+    /// This error occurs when the signature of a broadcast message does not validate
+    #[cfg_attr(feature = "std", error("Invalid signature"))]
+    InvalidSignature,
+    /// This error occurs when a secret is reconstructed from fewer shares than the given
+    /// number of required shares
+    #[cfg_attr(feature = "std", error("Insufficient shares, {} are required"))]
+    InsufficientShares(usize),
+    /// This error occurs when a share has index zero, or the same index as another share
+    #[cfg_attr(feature = "std", error("Invalid share index {}"))]
+    InvalidShareIndex(usize),
+    /// This error occurs when the secret shares of the given members are not consistent with
+    /// their public shares
+    #[cfg_attr(feature = "std", error("Invalid secret shares of members {:?}"))]
+    InvalidSecretShares(Vec<usize>),
+    /// This error occurs when the decryption shares of the given members do not validate
+    #[cfg_attr(feature = "std", error("Invalid decryption shares of members {:?}"))]
+    InvalidDecryptionShares(Vec<usize>),
+    /// This error occurs when a decrypted value is larger than the bound of the discrete
+    /// logarithm table
+    #[cfg_attr(
+        feature = "std",
+        error("Decrypted value out of the bounds of the table")
+    )]
+    DecryptionOutOfBounds,
+    /// This error occurs when a dealing does not commit to exactly `threshold + 1`
+    /// coefficients
+    #[cfg_attr(
+        feature = "std",
+        error("Invalid number of commitments, {} are required")
+    )]
+    InvalidNumberOfCommitments(usize),
 }
 
 impl From<ProofError> for DkgError {
@@ -1,12 +1,13 @@
-use crate::traits::{PrimeGroupElement, Scalar};
+use crate::traits::{FixedBaseTable, PrimeGroupElement, Scalar};
 use blake2::Digest;
-use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
-use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::constants::{RISTRETTO_BASEPOINT_POINT, RISTRETTO_BASEPOINT_TABLE};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoBasepointTable, RistrettoPoint};
 use curve25519_dalek::scalar::Scalar as RScalar;
 use curve25519_dalek::traits::{Identity, VartimeMultiscalarMul};
 use generic_array::typenum::{U32, U64};
 use generic_array::GenericArray;
 use rand_core::{CryptoRng, RngCore};
+use std::sync::Arc;
 
 impl Scalar for RScalar {
     type Item = RScalar;
@@ -47,6 +48,10 @@ impl Scalar for RScalar {
         self.invert()
     }
 
+    fn batch_inverse(scalars: &mut [Self]) {
+        RScalar::batch_invert(scalars);
+    }
+
     fn hash_to_scalar<H: Digest<OutputSize = U64> + Default>(input: &[u8]) -> Self {
         RScalar::hash_from_bytes::<H>(input)
     }
@@ -61,6 +66,14 @@ impl PrimeGroupElement for RistrettoPoint {
         RISTRETTO_BASEPOINT_POINT
     }
 
+    fn generator_mul(scalar: &Self::CorrespondingScalar) -> Self {
+        &RISTRETTO_BASEPOINT_TABLE * scalar
+    }
+
+    fn fixed_base_table(&self) -> Option<Arc<dyn FixedBaseTable<Self>>> {
+        Some(Arc::new(RistrettoBasepointTable::create(self)))
+    }
+
     fn zero() -> Self {
         RistrettoPoint::identity()
     }
@@ -88,3 +101,9 @@ impl PrimeGroupElement for RistrettoPoint {
         RistrettoPoint::vartime_multiscalar_mul(scalars.into_iter(), points.into_iter())
     }
 }
+
+impl FixedBaseTable<RistrettoPoint> for RistrettoBasepointTable {
+    fn mul(&self, scalar: &RScalar) -> RistrettoPoint {
+        self * scalar
+    }
+}
@@ -55,6 +55,21 @@
 //! be done again by exploiting the additive homomorphic property of the commitment
 //! scheme.
 //!
+//! # Features
+//!
+//! * `zeroize`: erases secret keys, secret shares, symmetric keys and the sharing polynomials
+//!   from memory when they are dropped, with the `erase` methods of `Scalar` and
+//!   `PrimeGroupElement`. These types do not implement the `zeroize::ZeroizeOnDrop` marker,
+//!   which needs zeroize 1.5, while curve25519-dalek 3 requires a version below 1.4.
+//! * `rayon`: processes the shares of every member in parallel, when encrypting them in
+//!   `Phases::init`, decrypting them and checking them against the committed coefficients.
+//!   The output of the protocol is the same as without the feature for the same randomness.
+//!   With this feature enabled, these phases require the group elements, scalars and fixed
+//!   base tables to be `Send` and `Sync`.
+//! * `bench`: implements `Clone` for the member states, which copies their secrets. It is
+//!   required by the `dkg` benchmark, e.g. `cargo bench --features bench`, and should not be
+//!   enabled otherwise.
+//!
 //! # Example
 //!
 //! ```rust
@@ -74,19 +89,18 @@
 //!         let shared_string = b"Example of a shared string.".to_owned();
 //!         let threshold = 1;
 //!         let nr_members = 3;
-//!         let environment = Environment::init(threshold, nr_members, &shared_string);
+//!         let environment = Environment::init(threshold, nr_members, &shared_string, b"Example session");
 //!
-//!         let mc1 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-//!         let mc2 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
-//!         let mc3 = MemberCommunicationKey::<RistrettoPoint>::new(&mut rng);
+//!         let keys = MemberCommunicationKey::<RistrettoPoint>::generate_committee(&mut rng, 3);
+//!         let (mc1, mc2, mc3) = (&keys[0], &keys[1], &keys[2]);
 //!         let mc = [mc1.to_public(), mc2.to_public(), mc3.to_public()];
 //!
 //!         let (m1, broad_1) =
-//!             DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc1, &mc, 1);
+//!             DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc1, &mc, 1);
 //!         let (m2, broad_2) =
-//!             DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc2, &mc, 2);
+//!             DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc2, &mc, 2);
 //!         let (m3, broad_3) =
-//!             DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, &mc3, &mc, 3);
+//!             DistributedKeyGeneration::<RistrettoPoint>::init(&mut rng, &environment, mc3, &mc, 3);
 //!
 //!         // Parties 1, 2, and 3 publish broad_1, broad_2, and broad_3 respectively in the
 //!         // blockchain. All parties fetched the data.
@@ -195,5 +209,7 @@ pub mod cryptography;
 pub mod dkg;
 pub mod errors;
 mod groups;
+mod parallel;
 pub mod polynomial;
 pub mod traits;
+pub mod vss;
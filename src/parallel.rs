@@ -0,0 +1,25 @@
+//! Processing of independent items, in parallel if the `rayon` feature is enabled, and
+//! sequentially otherwise. In both cases the results are returned in the order of the input,
+//! so that the outcome of the protocol does not depend on the feature.
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Apply `f` to every element of `items`.
+#[cfg(feature = "rayon")]
+pub(crate) fn map<T, U, F>(items: &[T], f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Send + Sync,
+{
+    items.par_iter().map(f).collect()
+}
+
+/// Apply `f` to every element of `items`.
+#[cfg(not(feature = "rayon"))]
+pub(crate) fn map<T, U, F>(items: &[T], f: F) -> Vec<U>
+where
+    F: Fn(&T) -> U,
+{
+    items.iter().map(f).collect()
+}
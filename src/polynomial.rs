@@ -3,6 +3,7 @@
 
 use crate::traits::Scalar;
 use rand_core::{CryptoRng, RngCore};
+use subtle::{ConditionallySelectable, ConstantTimeEq};
 
 /// A polynomial of specific degree d
 ///
@@ -12,6 +13,22 @@ pub struct Polynomial<S: Scalar> {
     elements: Box<[S]>,
 }
 
+#[cfg(feature = "zeroize")]
+impl<S: Scalar> zeroize::Zeroize for Polynomial<S> {
+    fn zeroize(&mut self) {
+        for coefficient in self.elements.iter_mut() {
+            coefficient.erase();
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<S: Scalar> Drop for Polynomial<S> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
 impl<S: Scalar> std::fmt::Display for Polynomial<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for (d, coef) in self.elements.iter().enumerate().rev() {
@@ -26,24 +43,23 @@ impl<S: Scalar> std::fmt::Display for Polynomial<S> {
 }
 
 impl<S: Scalar> Polynomial<S> {
-    /// Generate a new 0 polynomial of specific degree
+    /// Generate a new 0 polynomial, with capacity for the coefficients of a polynomial of
+    /// degree `degree`
     pub fn new(degree: usize) -> Self {
         Self {
             elements: std::iter::repeat(S::zero()).take(degree + 1).collect(),
         }
     }
 
-    pub fn set2(mut self, x0: S, x1: S) -> Self {
-        assert!(self.degree() >= 1);
-        self.elements[0] = x0;
-        self.elements[1] = x1;
-        self
-    }
-
-    /// Return the degree of the polynomial
-    pub fn degree(&self) -> usize {
-        assert!(!self.elements.is_empty());
-        self.elements.len() - 1
+    /// Initialize from the coefficients of the terms, starting from the lowest degree. An
+    /// empty slice results in the zero polynomial.
+    pub fn from_coefficients(coefficients: &[S]) -> Self {
+        if coefficients.is_empty() {
+            return Self::new(0);
+        }
+        Polynomial {
+            elements: coefficients.into(),
+        }
     }
 
     /// Initialize from a vector, where each element represent the term coefficient
@@ -64,13 +80,110 @@ impl<S: Scalar> Polynomial<S> {
         }
     }
 
-    /// get the value of a polynomial a0 + a1 * x^1 + a2 * x^2 + .. + an * x^n for a value x=at
+    /// Generate a random polynomial of specific degree, whose constant term is `secret`. This
+    /// is the polynomial used by a dealer to share a chosen secret.
+    pub fn random_with_secret<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        degree: usize,
+        secret: S,
+    ) -> Self {
+        Self::random(rng, degree).with_constant_term(secret)
+    }
+
+    /// Set the constant term of the polynomial, i.e. its value at zero.
+    pub fn with_constant_term(self, constant: S) -> Self {
+        self.with_coefficient(0, constant)
+    }
+
+    /// Set the coefficient of the term of degree `degree`, extending the capacity of the
+    /// polynomial if needed.
+    pub fn with_coefficient(mut self, degree: usize, coefficient: S) -> Self {
+        if degree >= self.elements.len() {
+            // Allocated at its final size, so that growing it leaves no copy of the
+            // coefficients behind.
+            let mut elements = Vec::with_capacity(degree + 1);
+            elements.extend_from_slice(&self.elements);
+            elements.resize(degree + 1, S::zero());
+
+            #[cfg(feature = "zeroize")]
+            zeroize::Zeroize::zeroize(&mut self);
+            self.elements = elements.into_boxed_slice();
+        }
+        self.elements[degree] = coefficient;
+        self
+    }
+
+    /// Set the constant term and the coefficient of degree one.
+    pub fn set2(self, x0: S, x1: S) -> Self {
+        self.with_coefficient(0, x0).with_coefficient(1, x1)
+    }
+
+    /// Return the degree of the polynomial, i.e. the highest degree with a non-zero
+    /// coefficient. The degree of the zero polynomial is zero. As the polynomial may be
+    /// secret, every coefficient is inspected, in constant time.
+    pub fn degree(&self) -> usize {
+        let zero = S::zero().to_bytes();
+        let mut degree = 0u64;
+        for (i, e) in self.elements.iter().enumerate() {
+            let is_zero = e.to_bytes()[..].ct_eq(&zero[..]);
+            degree = u64::conditional_select(&(i as u64), &degree, is_zero);
+        }
+        degree as usize
+    }
+
+    /// Return the number of stored coefficients, which can be larger than `degree() + 1` if
+    /// the leading coefficients are zero.
+    pub fn capacity(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// get the value of a polynomial a0 + a1 * x^1 + a2 * x^2 + .. + an * x^n for a value x=at,
+    /// using Horner's rule, a0 + x * (a1 + x * (a2 + .. + x * an))
     pub fn evaluate(&self, at: &S) -> S {
         self.elements
             .iter()
-            .zip(at.exp_iter())
-            .map(|(&e, x)| e * x)
-            .fold(S::zero(), |a, b| a + b)
+            .rev()
+            .fold(S::zero(), |acc, &e| acc * *at + e)
+    }
+
+    /// Evaluate the polynomial at every value in `points`. If the points are in arithmetic
+    /// progression (e.g. the indices `1, 2, .., n` of the members), only the first `degree + 1`
+    /// points are evaluated, and the remaining evaluations are computed with additions, using
+    /// the forward differences of the polynomial. Otherwise, every point is evaluated with
+    /// `evaluate`.
+    pub fn evaluate_many(&self, points: &[S]) -> Vec<S> {
+        let degree = self.degree();
+        let is_progression =
+            points.len() > degree + 1 && points.windows(3).all(|w| w[2] - w[1] == w[1] - w[0]);
+        if !is_progression {
+            return points.iter().map(|point| self.evaluate(point)).collect();
+        }
+
+        // After this loop, `differences[k]` is the k-th forward difference at `points[0]`.
+        let mut differences: Vec<S> = points[..=degree]
+            .iter()
+            .map(|point| self.evaluate(point))
+            .collect();
+        for k in 1..=degree {
+            for i in (k..=degree).rev() {
+                differences[i] = differences[i] - differences[i - 1];
+            }
+        }
+
+        // The `degree`-th difference is constant, and every step moves the lower differences
+        // to the next point.
+        let mut evaluations = Vec::with_capacity(points.len());
+        for _ in points {
+            evaluations.push(differences[0]);
+            for k in 0..degree {
+                differences[k] = differences[k] + differences[k + 1];
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        differences.iter_mut().for_each(S::erase);
+
+        evaluations
     }
 
     /// Evaluate the polynomial at x=0
@@ -86,27 +199,95 @@ impl<S: Scalar> Polynomial<S> {
         self.elements.iter()
     }
 
+    /// Returns true if all the coefficients of the polynomial are zero
+    pub fn is_zero(&self) -> bool {
+        self.elements.iter().all(|&e| e == S::zero())
+    }
+
+    /// Remove the zero leading coefficients, so that the capacity of the polynomial is
+    /// `degree() + 1`. The zero polynomial keeps a single coefficient.
+    pub fn normalise(&mut self) {
+        let length = self.degree() + 1;
+        if length == self.elements.len() {
+            return;
+        }
+        let trimmed = self.elements[..length].to_vec().into_boxed_slice();
+
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(self);
+        self.elements = trimmed;
+    }
+
+    /// Polynomial long division. Returns the quotient and the remainder of the division by
+    /// `divisor`, such that `self = quotient * divisor + remainder`, where the degree of the
+    /// remainder is smaller than that of the divisor. Both are returned normalised.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is the zero polynomial.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let mut divisor = divisor.clone();
+        divisor.normalise();
+        assert!(!divisor.is_zero(), "Division by the zero polynomial");
+
+        let mut remainder = self.clone();
+        remainder.normalise();
+        if remainder.degree() < divisor.degree() {
+            return (Self::new(0), remainder);
+        }
+
+        let divisor_degree = divisor.degree();
+        let leading_inverse = divisor.elements[divisor_degree].inverse();
+        let mut quotient = Self::new(remainder.degree() - divisor_degree);
+        for k in (0..quotient.elements.len()).rev() {
+            let coefficient = remainder.elements[k + divisor_degree] * leading_inverse;
+            quotient.elements[k] = coefficient;
+            for (j, &d) in divisor.elements.iter().enumerate() {
+                remainder.elements[k + j] = remainder.elements[k + j] - coefficient * d;
+            }
+        }
+
+        quotient.normalise();
+        remainder.normalise();
+        (quotient, remainder)
+    }
+
     /// Given indices //(x_1, \ldots, x_n//), and evaluated points //(y_1, \ldots, y_n//), one can
     /// compute a polynomial of degree //(n - 1//) by computing the following:
     /// //(P(x) = \sum_{i=1}^n y_i\prod_{k=1, k\neq i}^n\frac{x - x_k}{x_j - x_k}.//)
+    ///
+    /// The product //(M(x) = \prod_{k=1}^n (x - x_k)//) is computed once, and every term of the
+    /// sum is obtained by dividing //(M(x)//) by //((x - x_i)//), with the denominators inverted
+    /// in a batch.
     pub fn interpolate(degree: usize, evaluated_points: &[S], indices: &[S]) -> Self {
         assert_eq!(degree + 1, evaluated_points.len());
         assert_eq!(degree + 1, indices.len());
-        let mut polynomial = Self::from_vec(vec![S::zero()]);
-
-        for i in 0..degree + 1 {
-            let mut tem_polynomial = Self::from_vec(vec![evaluated_points[i]]);
-            for j in 0..degree + 1 {
-                if i == j {
-                    continue;
-                }
-                tem_polynomial =
-                    tem_polynomial * Self::from_vec(vec![(indices[i] - indices[j]).inverse()]);
-                tem_polynomial = tem_polynomial * Self::from_vec(vec![-indices[j], S::one()]);
+
+        // Coefficients of M(x), from the lowest degree.
+        let mut master = vec![S::one()];
+        for &index in indices {
+            master.push(S::zero());
+            for k in (1..master.len()).rev() {
+                master[k] = master[k - 1] - index * master[k];
             }
-            polynomial = polynomial + tem_polynomial;
+            master[0] = -index * master[0];
         }
-        polynomial
+
+        let weights = barycentric_weights(indices);
+
+        let mut elements = vec![S::zero(); degree + 1];
+        for ((&index, &weight), &evaluation) in
+            indices.iter().zip(weights.iter()).zip(evaluated_points)
+        {
+            // Synthetic division of M(x) by (x - index), from the highest degree.
+            let scale = weight * evaluation;
+            let mut quotient = S::zero();
+            for k in (0..=degree).rev() {
+                quotient = master[k + 1] + index * quotient;
+                elements[k] += scale * quotient;
+            }
+        }
+        Self::from_vec(elements)
     }
 }
 
@@ -125,30 +306,71 @@ impl<S: Scalar> AsMut<[S]> for Polynomial<S> {
 impl<S: Scalar> std::ops::Add<Polynomial<S>> for Polynomial<S> {
     type Output = Polynomial<S>;
 
-    fn add(self, rhs: Polynomial<S>) -> Self::Output {
-        if self.degree() >= rhs.degree() {
-            let mut x = self.elements;
-            for (e, r) in x.iter_mut().zip(rhs.elements.iter()) {
-                *e += *r;
-            }
-            Self { elements: x }
-        } else {
-            let mut x = rhs.elements;
-            for (e, r) in x.iter_mut().zip(self.elements.iter()) {
-                *e += *r;
-            }
-            Self { elements: x }
+    fn add(mut self, mut rhs: Polynomial<S>) -> Self::Output {
+        if self.capacity() < rhs.capacity() {
+            std::mem::swap(&mut self, &mut rhs);
+        }
+        for (e, r) in self.elements.iter_mut().zip(rhs.elements.iter()) {
+            *e += *r;
         }
+        self
     }
 }
 
+impl<S: Scalar> std::ops::Neg for Polynomial<S> {
+    type Output = Polynomial<S>;
+
+    fn neg(mut self) -> Self::Output {
+        for e in self.elements.iter_mut() {
+            *e = -*e;
+        }
+        self
+    }
+}
+
+impl<S: Scalar> std::ops::Sub<Polynomial<S>> for Polynomial<S> {
+    type Output = Polynomial<S>;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Polynomial<S>) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl<S: Scalar> std::ops::Mul<S> for Polynomial<S> {
+    type Output = Polynomial<S>;
+
+    fn mul(mut self, rhs: S) -> Self::Output {
+        for e in self.elements.iter_mut() {
+            *e = *e * rhs;
+        }
+        self
+    }
+}
+
+/// Two polynomials are equal if they have the same coefficients, ignoring the zero leading
+/// ones. As polynomials may hold secret material, coefficients are compared in constant time.
+impl<S: Scalar> PartialEq for Polynomial<S> {
+    fn eq(&self, other: &Self) -> bool {
+        let length = std::cmp::max(self.elements.len(), other.elements.len());
+        let zero = S::zero();
+        (0..length).fold(true, |equal, i| {
+            let left = self.elements.get(i).unwrap_or(&zero);
+            let right = other.elements.get(i).unwrap_or(&zero);
+            equal & left.constant_time_eq(right)
+        })
+    }
+}
+
+impl<S: Scalar> Eq for Polynomial<S> {}
+
 impl<S: Scalar> std::ops::Mul<Polynomial<S>> for Polynomial<S> {
     type Output = Polynomial<S>;
 
     #[allow(clippy::suspicious_arithmetic_impl)]
     fn mul(self, rhs: Polynomial<S>) -> Self::Output {
         //println!("muling {} * {}", self, rhs);
-        let mut result = Self::new(self.degree() + rhs.degree());
+        let mut result = Self::new(self.capacity() + rhs.capacity() - 2);
         for (left_degree, &left_coeff) in self.elements.iter().enumerate() {
             for (right_degree, &right_coeff) in rhs.elements.iter().enumerate() {
                 let degree = left_degree + right_degree;
@@ -159,14 +381,87 @@ impl<S: Scalar> std::ops::Mul<Polynomial<S>> for Polynomial<S> {
     }
 }
 
-fn lagrange_coefficient<S: Scalar>(evaluation_point: S, coefficient_index: S, indices: &[S]) -> S {
-    let mut result = S::one();
-    for &i in indices {
-        if i != coefficient_index {
-            result = result * (evaluation_point - i) * (coefficient_index - i).inverse();
+// Returns the weights //(w_i = \prod_{k\neq i}(x_i - x_k)^{-1}//) of the indices, with a single
+// batched inversion.
+fn barycentric_weights<S: Scalar>(indices: &[S]) -> Vec<S> {
+    let mut weights: Vec<S> = indices
+        .iter()
+        .enumerate()
+        .map(|(i, &x_i)| {
+            indices
+                .iter()
+                .enumerate()
+                .filter(|&(k, _)| k != i)
+                .fold(S::one(), |product, (_, &x_k)| product * (x_i - x_k))
+        })
+        .collect();
+    S::batch_inverse(&mut weights);
+    weights
+}
+
+/// Lagrange coefficients of a fixed set of indices, //(x_1, \ldots, x_n//), at an evaluation
+/// point //(x//), //(\lambda_i = \prod_{k\neq i}\frac{x - x_k}{x_i - x_k}//). Once computed, the
+/// basis interpolates any set of evaluated points over the same indices with `n`
+/// multiplications.
+#[derive(Clone, Debug)]
+pub struct LagrangeBasis<S: Scalar> {
+    evaluation_point: S,
+    indices: Vec<S>,
+    coefficients: Vec<S>,
+}
+
+impl<S: Scalar> LagrangeBasis<S> {
+    /// Compute the Lagrange coefficients of `indices` at `evaluation_point`. The indices must
+    /// be distinct.
+    pub fn new(evaluation_point: S, indices: &[S]) -> Self {
+        let weights = barycentric_weights(indices);
+
+        // prefixes[i] = (x - x_1) * .. * (x - x_{i - 1}), and the suffix products are
+        // accumulated backwards.
+        let mut prefixes = Vec::with_capacity(indices.len());
+        let mut accumulator = S::one();
+        for &index in indices {
+            prefixes.push(accumulator);
+            accumulator = accumulator * (evaluation_point - index);
+        }
+
+        let mut coefficients = vec![S::zero(); indices.len()];
+        let mut suffix = S::one();
+        for i in (0..indices.len()).rev() {
+            coefficients[i] = prefixes[i] * suffix * weights[i];
+            suffix = suffix * (evaluation_point - indices[i]);
+        }
+
+        LagrangeBasis {
+            evaluation_point,
+            indices: indices.to_vec(),
+            coefficients,
         }
     }
-    result
+
+    pub fn evaluation_point(&self) -> &S {
+        &self.evaluation_point
+    }
+
+    pub fn indices(&self) -> &[S] {
+        &self.indices
+    }
+
+    pub fn coefficients(&self) -> &[S] {
+        &self.coefficients
+    }
+
+    /// Returns the value at the evaluation point of the polynomial of degree `n - 1` which
+    /// takes the values `evaluated_points` at the indices of the basis.
+    pub fn interpolate(&self, evaluated_points: &[S]) -> S {
+        assert_eq!(evaluated_points.len(), self.indices.len());
+        self.coefficients
+            .iter()
+            .zip(evaluated_points.iter())
+            .fold(S::zero(), |result, (&coefficient, &y)| {
+                result + coefficient * y
+            })
+    }
 }
 
 pub fn lagrange_interpolation<S: Scalar>(
@@ -175,18 +470,20 @@ pub fn lagrange_interpolation<S: Scalar>(
     indices: &[S],
 ) -> S {
     assert_eq!(evaluated_points.len(), indices.len());
-    let mut result = S::zero();
-    for (&x, &y) in indices.iter().zip(evaluated_points.iter()) {
-        let lagrange_coefficient = lagrange_coefficient(evaluation_point, x, indices);
-        result += lagrange_coefficient * y;
-    }
-    result
+    LagrangeBasis::new(evaluation_point, indices).interpolate(evaluated_points)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use blake2::Digest;
     use curve25519_dalek::scalar::Scalar as RScalar;
+    use derive_more::{Add, AddAssign, Mul, Neg, Sub};
+    use generic_array::typenum::U64;
+    #[allow(deprecated)]
+    use generic_array::GenericArray;
+    use rand_core::OsRng;
+
     #[test]
     fn lagrange() {
         let polynomial =
@@ -229,19 +526,157 @@ mod tests {
         let interpolated_polynomial =
             Polynomial::<RScalar>::interpolate(2, &evaluated_points, &indices);
 
-        for (a, b) in polynomial
+        assert!(polynomial == interpolated_polynomial);
+    }
+
+    // Scalar which uses the provided implementation of `batch_inverse`.
+    #[derive(Add, Sub, Neg, Mul, AddAssign, Clone, Copy, Debug, Eq, PartialEq)]
+    #[mul(forward)]
+    struct Wrapper(RScalar);
+
+    #[allow(deprecated)]
+    impl Scalar for Wrapper {
+        type Item = Wrapper;
+        type EncodingSize = <RScalar as Scalar>::EncodingSize;
+
+        fn random<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+            Wrapper(RScalar::random(rng))
+        }
+
+        fn hash_to_scalar<H: Digest<OutputSize = U64> + Default>(input: &[u8]) -> Self {
+            Wrapper(RScalar::hash_from_bytes::<H>(input))
+        }
+
+        fn from_u64(scalar: u64) -> Self {
+            Wrapper(RScalar::from(scalar))
+        }
+
+        fn to_bytes(&self) -> GenericArray<u8, Self::EncodingSize> {
+            Scalar::to_bytes(&self.0)
+        }
+
+        fn from_bytes(bytes: &[u8]) -> Option<Self> {
+            <RScalar as Scalar>::from_bytes(bytes).map(Wrapper)
+        }
+
+        fn zero() -> Self {
+            Wrapper(RScalar::zero())
+        }
+
+        fn one() -> Self {
+            Wrapper(RScalar::one())
+        }
+
+        fn inverse(&self) -> Self {
+            Wrapper(self.0.invert())
+        }
+    }
+
+    #[test]
+    fn batch_inversion() {
+        let mut rng = OsRng;
+        let scalars: Vec<RScalar> = (0..10).map(|_| RScalar::random(&mut rng)).collect();
+        let expected: Vec<RScalar> = scalars.iter().map(|s| s.inverse()).collect();
+
+        // The overridden implementation of ristretto, and the provided one.
+        let mut inverses = scalars.clone();
+        <RScalar as Scalar>::batch_inverse(&mut inverses);
+        assert_eq!(inverses, expected);
+
+        let mut wrapped: Vec<Wrapper> = scalars.into_iter().map(Wrapper).collect();
+        Wrapper::batch_inverse(&mut wrapped);
+        assert!(wrapped.iter().zip(expected.iter()).all(|(w, e)| w.0 == *e));
+
+        Wrapper::batch_inverse(&mut []);
+    }
+
+    #[test]
+    fn lagrange_basis() {
+        let mut rng = OsRng;
+        let polynomial = Polynomial::<RScalar>::random(&mut rng, 4);
+        let indices: Vec<RScalar> = [3u64, 1, 7, 4, 10]
+            .iter()
+            .map(|&i| RScalar::from_u64(i))
+            .collect();
+        let evaluation_point = RScalar::random(&mut rng);
+
+        let basis = LagrangeBasis::new(evaluation_point, &indices);
+        for (i, &x_i) in indices.iter().enumerate() {
+            let expected = indices
+                .iter()
+                .filter(|&&x_k| x_k != x_i)
+                .fold(RScalar::one(), |product, &x_k| {
+                    product * (evaluation_point - x_k) * (x_i - x_k).inverse()
+                });
+            assert_eq!(basis.coefficients()[i], expected);
+        }
+
+        let evaluated_points = polynomial.evaluate_many(&indices);
+        assert_eq!(
+            basis.interpolate(&evaluated_points),
+            polynomial.evaluate(&evaluation_point)
+        );
+
+        // The basis is reused for a different polynomial over the same indices.
+        let other = Polynomial::<RScalar>::random(&mut rng, 4);
+        assert_eq!(
+            basis.interpolate(&other.evaluate_many(&indices)),
+            other.evaluate(&evaluation_point)
+        );
+
+        // Evaluating at one of the indices returns the corresponding evaluated point.
+        let basis = LagrangeBasis::new(indices[2], &indices);
+        assert_eq!(basis.interpolate(&evaluated_points), evaluated_points[2]);
+
+        let interpolated_polynomial = Polynomial::interpolate(4, &evaluated_points, &indices);
+        assert!(polynomial == interpolated_polynomial);
+    }
+
+    // Evaluation as a sum of the coefficients multiplied by the powers of the point.
+    fn naive_evaluation(polynomial: &Polynomial<RScalar>, at: &RScalar) -> RScalar {
+        polynomial
             .get_coefficients()
-            .zip(interpolated_polynomial.get_coefficients())
-        {
-            assert_eq!(a, b);
+            .zip(at.exp_iter())
+            .map(|(&e, x)| e * x)
+            .fold(RScalar::zero(), |a, b| a + b)
+    }
+
+    #[test]
+    fn evaluation() {
+        let mut rng = OsRng;
+        let polynomial = Polynomial::<RScalar>::random(&mut rng, 7);
+
+        let at = RScalar::random(&mut rng);
+        assert_eq!(polynomial.evaluate(&at), naive_evaluation(&polynomial, &at));
+
+        // Points in arithmetic progression, as the indices of the members.
+        let indices: Vec<RScalar> = (1..=20).map(RScalar::from_u64).collect();
+        let shifted: Vec<RScalar> = (0..20).map(|i| at + RScalar::from_u64(3 * i)).collect();
+        // Arbitrary points.
+        let random: Vec<RScalar> = (0..20).map(|_| RScalar::random(&mut rng)).collect();
+
+        for points in [indices, shifted, random].iter() {
+            let expected: Vec<RScalar> = points
+                .iter()
+                .map(|point| naive_evaluation(&polynomial, point))
+                .collect();
+            assert_eq!(polynomial.evaluate_many(points), expected);
         }
+
+        let constant = Polynomial::<RScalar>::from_vec(vec![at]);
+        assert_eq!(
+            constant.evaluate_many(&[RScalar::one(), RScalar::from_u64(2), RScalar::from_u64(3)]),
+            vec![at; 3]
+        );
+        assert!(polynomial.evaluate_many(&[]).is_empty());
     }
 
     #[test]
     fn poly_tests() {
         let poly_deg_4 = Polynomial::<RScalar>::new(4).set2(RScalar::one(), RScalar::from_u64(3));
 
-        assert_eq!(poly_deg_4.degree(), 4);
+        assert_eq!(poly_deg_4.degree(), 1);
+        assert_eq!(poly_deg_4.capacity(), 5);
         assert_eq!(
             poly_deg_4.evaluate(&RScalar::from_u64(3)),
             RScalar::from_u64(10)
@@ -255,12 +690,7 @@ mod tests {
         let expected_poly =
             Polynomial::<RScalar>::from_vec(vec![RScalar::from_u64(14), RScalar::from_u64(5)]);
 
-        for (a, b) in added_polys
-            .get_coefficients()
-            .zip(expected_poly.get_coefficients())
-        {
-            assert_eq!(a, b);
-        }
+        assert!(added_polys == expected_poly);
 
         let mult_poly = poly_deg_4 * poly_deg_2;
 
@@ -270,11 +700,102 @@ mod tests {
             RScalar::from_u64(6),
         ]);
 
-        for (a, b) in mult_poly
-            .get_coefficients()
-            .zip(expected_mult.get_coefficients())
-        {
-            assert_eq!(a, b);
-        }
+        assert!(mult_poly == expected_mult);
+    }
+
+    #[test]
+    fn constructors() {
+        let mut rng = OsRng;
+        let secret = RScalar::random(&mut rng);
+
+        let polynomial = Polynomial::random_with_secret(&mut rng, 3, secret);
+        assert_eq!(polynomial.degree(), 3);
+        assert_eq!(polynomial.at_zero(), secret);
+
+        let coefficients = [RScalar::from_u64(2), RScalar::zero(), RScalar::from_u64(5)];
+        let polynomial = Polynomial::from_coefficients(&coefficients);
+        assert!(polynomial.get_coefficients().eq(coefficients.iter()));
+        assert_eq!(
+            polynomial.evaluate(&RScalar::from_u64(2)),
+            RScalar::from_u64(22)
+        );
+
+        // The degree ignores the zero leading coefficients, and the capacity does not.
+        let polynomial = Polynomial::<RScalar>::new(4).with_constant_term(secret);
+        assert_eq!(polynomial.degree(), 0);
+        assert_eq!(polynomial.capacity(), 5);
+        assert!(polynomial == Polynomial::from_coefficients(&[secret]));
+
+        let polynomial = Polynomial::<RScalar>::from_coefficients(&[])
+            .with_coefficient(3, RScalar::one())
+            .with_constant_term(secret);
+        assert_eq!(polynomial.degree(), 3);
+        assert_eq!(polynomial.capacity(), 4);
+        assert_eq!(
+            polynomial.evaluate(&RScalar::one()),
+            secret + RScalar::one()
+        );
+        assert!(Polynomial::<RScalar>::from_coefficients(&[]).is_zero());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut rng = OsRng;
+        let a = Polynomial::<RScalar>::random(&mut rng, 5);
+        let b = Polynomial::<RScalar>::random(&mut rng, 2);
+        let scalar = RScalar::random(&mut rng);
+        let at = RScalar::random(&mut rng);
+
+        let difference = a.clone() - b.clone();
+        assert_eq!(difference.evaluate(&at), a.evaluate(&at) - b.evaluate(&at));
+        assert!(difference + b.clone() == a);
+        assert!((-a.clone()).evaluate(&at) == -a.evaluate(&at));
+        assert!((a.clone() - a.clone()).is_zero());
+        assert_eq!((a.clone() * scalar).evaluate(&at), a.evaluate(&at) * scalar);
+
+        // Equality ignores the zero leading coefficients, which normalisation removes.
+        let mut padded = Polynomial::from_vec(vec![scalar, RScalar::one(), RScalar::zero()]);
+        let expected = Polynomial::from_vec(vec![scalar, RScalar::one()]);
+        assert!(padded == expected);
+        assert!(padded != Polynomial::from_vec(vec![scalar]));
+        assert_eq!(padded.capacity(), 3);
+        padded.normalise();
+        assert_eq!(padded.capacity(), 2);
+        assert_eq!(padded.degree(), 1);
+        let mut zero = Polynomial::<RScalar>::new(3);
+        zero.normalise();
+        assert_eq!(zero.capacity(), 1);
+        assert!(zero.is_zero());
+    }
+
+    #[test]
+    fn division() {
+        let mut rng = OsRng;
+        let dividend = Polynomial::<RScalar>::random(&mut rng, 7);
+        let divisor = Polynomial::<RScalar>::random(&mut rng, 3);
+
+        let (quotient, remainder) = dividend.div_rem(&divisor);
+        assert_eq!(quotient.degree(), 4);
+        assert!(remainder.degree() < divisor.degree());
+        assert!(quotient * divisor.clone() + remainder == dividend);
+
+        // Exact division by a linear factor of a polynomial.
+        let root = RScalar::random(&mut rng);
+        let factor = Polynomial::from_vec(vec![-root, RScalar::one()]);
+        let (quotient, remainder) = (dividend.clone() * factor.clone()).div_rem(&factor);
+        assert!(quotient == dividend);
+        assert!(remainder.is_zero());
+
+        // A divisor of higher degree leaves the dividend as remainder.
+        let (quotient, remainder) = divisor.div_rem(&dividend);
+        assert!(quotient.is_zero());
+        assert!(remainder == divisor);
+    }
+
+    #[test]
+    #[should_panic]
+    fn division_by_zero() {
+        let polynomial = Polynomial::<RScalar>::random(&mut OsRng, 2);
+        polynomial.div_rem(&Polynomial::new(1));
     }
 }
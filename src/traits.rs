@@ -138,6 +138,35 @@ use generic_array::{ArrayLength, GenericArray};
 use rand_core::{CryptoRng, RngCore};
 use std::fmt::Debug;
 use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+
+/// Overwrite `value` with `zero`, with a volatile write followed by a compiler fence, so that
+/// the erasure of secret material is not optimised away. As `value` is `Copy`, it does not own
+/// memory other than its own representation.
+fn erase<T: Copy>(value: &mut T, zero: T) {
+    // SAFETY: `value` is a valid, aligned and exclusive reference.
+    unsafe { std::ptr::write_volatile(value, zero) };
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Bound of the phases of the protocol which process the shares of every member in parallel
+/// if the `rayon` feature is enabled, in which case the values processed need to be shareable
+/// across threads. Without the feature, every type satisfies it.
+#[cfg(feature = "rayon")]
+pub trait Parallelisable: Send + Sync {}
+
+#[cfg(feature = "rayon")]
+impl<T: Send + Sync> Parallelisable for T {}
+
+/// Bound of the phases of the protocol which process the shares of every member in parallel
+/// if the `rayon` feature is enabled, in which case the values processed need to be shareable
+/// across threads. Without the feature, every type satisfies it.
+#[cfg(not(feature = "rayon"))]
+pub trait Parallelisable {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T> Parallelisable for T {}
 
 pub trait Scalar:
     Copy
@@ -169,6 +198,41 @@ pub trait Scalar:
 
     fn inverse(&self) -> Self;
 
+    /// Overwrite the scalar with zero, to erase secret material from memory when the `zeroize`
+    /// feature is enabled. Implementations can override it, e.g. with `zeroize::Zeroize`.
+    fn erase(&mut self) {
+        erase(self, Self::zero())
+    }
+
+    /// Replace every element of `scalars` by its inverse, with Montgomery's trick, which
+    /// computes a single inversion and `3 * (n - 1)` multiplications. All elements must be
+    /// non-zero. Implementations can override it if the scalar field has a faster variant.
+    fn batch_inverse(scalars: &mut [Self]) {
+        if scalars.is_empty() {
+            return;
+        }
+        // products[i] = scalars[0] * .. * scalars[i - 1]
+        let mut products = Vec::with_capacity(scalars.len());
+        let mut accumulator = Self::one();
+        for &scalar in scalars.iter() {
+            products.push(accumulator);
+            accumulator = accumulator * scalar;
+        }
+
+        // accumulator = (scalars[0] * .. * scalars[i])^{-1}
+        let mut accumulator = accumulator.inverse();
+        for (scalar, &product) in scalars.iter_mut().zip(products.iter()).rev() {
+            let inverse = accumulator * product;
+            accumulator = accumulator * *scalar;
+            *scalar = inverse;
+        }
+    }
+
+    /// Equality check which runs in constant time, to be used when comparing secret values.
+    fn constant_time_eq(&self, other: &Self) -> bool {
+        self.to_bytes()[..].ct_eq(&other.to_bytes()[..]).into()
+    }
+
     fn exp_iter(&self) -> ScalarExp<Self> {
         let next_exp_x = Self::one();
         ScalarExp {
@@ -201,6 +265,14 @@ impl<S: Scalar> Iterator for ScalarExp<S> {
     }
 }
 
+/// Precomputed data of a fixed base, which speeds up its multiplication by different scalars.
+/// Groups opt in to fixed base multiplications by implementing it for their table type, and
+/// returning it in `PrimeGroupElement::fixed_base_table`.
+pub trait FixedBaseTable<G: PrimeGroupElement>: Send + Sync {
+    /// Returns `base * scalar`, where `self` is the precomputed table of `base`.
+    fn mul(&self, scalar: &G::CorrespondingScalar) -> G;
+}
+
 pub trait PrimeGroupElement:
     Copy
     + Clone
@@ -223,14 +295,39 @@ pub trait PrimeGroupElement:
 
     fn generator() -> Self;
 
+    /// Returns `generator() * scalar`. Implementations should override it if the group has a
+    /// faster multiplication for the generator, e.g. using a precomputed table.
+    fn generator_mul(scalar: &Self::CorrespondingScalar) -> Self {
+        Self::generator() * *scalar
+    }
+
+    /// Precompute a table of `self`, which speeds up its multiplication by different scalars.
+    /// Returns `None` by default, in which case `self` is multiplied directly. Implementations
+    /// can override it if the group supports such precomputation.
+    fn fixed_base_table(&self) -> Option<Arc<dyn FixedBaseTable<Self>>> {
+        None
+    }
+
     fn zero() -> Self;
 
+    /// Overwrite the element with the identity, to erase secret material from memory when
+    /// the `zeroize` feature is enabled. Implementations can override it, e.g. with
+    /// `zeroize::Zeroize`.
+    fn erase(&mut self) {
+        erase(self, Self::zero())
+    }
+
     fn hash_to_group<H: Digest<OutputSize = U64> + Default>(input: &[u8]) -> Self;
 
     fn to_bytes(&self) -> GenericArray<u8, Self::EncodingSize>;
 
     fn from_bytes(bytes: &[u8]) -> Option<Self>;
 
+    /// Equality check which runs in constant time, to be used when comparing secret values.
+    fn constant_time_eq(&self, other: &Self) -> bool {
+        self.to_bytes()[..].ct_eq(&other.to_bytes()[..]).into()
+    }
+
     fn vartime_multiscalar_multiplication<I, J>(scalars: I, points: J) -> Self
     where
         I: IntoIterator<Item = Self::CorrespondingScalar>,
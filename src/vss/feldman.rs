@@ -0,0 +1,167 @@
+//! Feldman VSS. The dealer commits to the coefficients of the sharing polynomial,
+//! \\( f(x) = \sum_{l = 0}^t a_l x^l \\), with \\( A_l = g^{a_l} \\), and member \\( i \\) verifies
+//! its share, \\( s_i = f(i) \\), by checking that
+//! \\( g^{s_i} = \prod_{l = 0}^t A_l^{i^l}. \\)
+//!
+//! Note that \\( A_0 = g^{secret} \\), so the commitments reveal the public key of the secret.
+use super::{member_indices, Share};
+use crate::errors::DkgError;
+use crate::polynomial::Polynomial;
+use crate::traits::{PrimeGroupElement, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+/// Public part of a Feldman dealing, the commitments to the coefficients of the sharing
+/// polynomial, starting from the constant term.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dealing<G: PrimeGroupElement> {
+    commitments: Vec<G>,
+}
+
+impl<G: PrimeGroupElement> Dealing<G> {
+    /// Share `secret` among `nr_members` members, such that `threshold + 1` shares are
+    /// required to reconstruct it. Returns the dealing, and the shares of the members, where
+    /// the share at position `i - 1` belongs to member `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is not smaller than `nr_members`.
+    pub fn new<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        threshold: usize,
+        nr_members: usize,
+        secret: &G::CorrespondingScalar,
+    ) -> (Self, Vec<Share<G::CorrespondingScalar>>) {
+        assert!(threshold < nr_members);
+        let polynomial = Polynomial::random_with_secret(rng, threshold, *secret);
+
+        let shares = polynomial
+            .evaluate_many(&member_indices(nr_members))
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| Share {
+                index: i + 1,
+                value,
+            })
+            .collect();
+
+        (Self::commit(&polynomial), shares)
+    }
+
+    /// Commit to the coefficients of `polynomial`.
+    pub fn commit(polynomial: &Polynomial<G::CorrespondingScalar>) -> Self {
+        Dealing {
+            commitments: polynomial
+                .get_coefficients()
+                .map(|coefficient| G::generator_mul(coefficient))
+                .collect(),
+        }
+    }
+
+    /// Initialise a dealing of a polynomial of degree `threshold` from the commitments
+    /// published by the dealer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DkgError::InvalidNumberOfCommitments` if there are not exactly
+    /// `threshold + 1` commitments, as a polynomial of larger degree cannot be reconstructed
+    /// from `threshold + 1` shares.
+    pub fn from_commitments(threshold: usize, commitments: Vec<G>) -> Result<Self, DkgError> {
+        if commitments.len() != threshold + 1 {
+            return Err(DkgError::InvalidNumberOfCommitments(threshold + 1));
+        }
+        Ok(Dealing { commitments })
+    }
+
+    pub fn commitments(&self) -> &[G] {
+        &self.commitments
+    }
+
+    pub fn into_commitments(self) -> Vec<G> {
+        self.commitments
+    }
+
+    /// Verify that `share` is consistent with the commitments of the dealing.
+    pub fn verify_share(&self, share: &Share<G::CorrespondingScalar>) -> bool {
+        check_share(&self.commitments, share)
+    }
+}
+
+/// Verify that `share` is consistent with the `commitments` to the coefficients of the
+/// sharing polynomial of degree `threshold`, i.e.
+/// \\( g^{s_i} = \prod_{l = 0}^t A_l^{i^l}. \\) Returns false if there are not exactly
+/// `threshold + 1` commitments.
+pub fn verify_share<G: PrimeGroupElement>(
+    threshold: usize,
+    commitments: &[G],
+    share: &Share<G::CorrespondingScalar>,
+) -> bool {
+    commitments.len() == threshold + 1 && check_share(commitments, share)
+}
+
+// Check of the share against the commitments, whose length is the number of coefficients.
+fn check_share<G: PrimeGroupElement>(
+    commitments: &[G],
+    share: &Share<G::CorrespondingScalar>,
+) -> bool {
+    let index_pow = <G::CorrespondingScalar as Scalar>::from_u64(share.index as u64)
+        .exp_iter()
+        .take(commitments.len());
+
+    let check_element = G::generator_mul(&share.value);
+    let multi_scalar =
+        G::vartime_multiscalar_multiplication(index_pow, commitments.iter().copied());
+
+    check_element == multi_scalar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vss::reconstruct;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar as RScalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn feldman_dealing() {
+        let mut rng = OsRng;
+        let secret = RScalar::random(&mut rng);
+        let (dealing, mut shares) = Dealing::<RistrettoPoint>::new(&mut rng, 2, 5, &secret);
+
+        assert_eq!(dealing.commitments().len(), 3);
+        assert_eq!(
+            dealing.commitments()[0],
+            RistrettoPoint::generator() * secret
+        );
+        assert!(shares.iter().all(|share| dealing.verify_share(share)));
+        assert_eq!(reconstruct(2, &shares[2..]), Ok(secret));
+
+        // A share of a different member, or a different value, does not verify.
+        shares[0].index = 2;
+        assert!(!dealing.verify_share(&shares[0]));
+        shares[1].value += RScalar::one();
+        assert!(!dealing.verify_share(&shares[1]));
+
+        let received = Dealing::from_commitments(2, dealing.commitments().to_vec()).unwrap();
+        assert_eq!(received, dealing);
+        assert!(received.verify_share(&shares[2]));
+        assert!(verify_share(2, dealing.commitments(), &shares[2]));
+    }
+
+    #[test]
+    fn larger_degree_dealing() {
+        let mut rng = OsRng;
+        let secret = RScalar::random(&mut rng);
+        // A dealing of degree `threshold + 1`, whose shares are consistent with its
+        // commitments, but from which `threshold + 1` shares do not reconstruct the secret.
+        let (dealing, shares) = Dealing::<RistrettoPoint>::new(&mut rng, 3, 5, &secret);
+        assert!(dealing.verify_share(&shares[0]));
+        assert_ne!(reconstruct(2, &shares), Ok(secret));
+
+        assert!(!verify_share(2, dealing.commitments(), &shares[0]));
+        assert_eq!(
+            Dealing::from_commitments(2, dealing.into_commitments()),
+            Err(DkgError::InvalidNumberOfCommitments(3))
+        );
+    }
+}
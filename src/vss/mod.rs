@@ -0,0 +1,167 @@
+//! Verifiable Secret Sharing (VSS). A dealer shares a secret among `n` members, by
+//! evaluating a random polynomial of degree `t`, whose constant term is the secret, at the
+//! indices `1, .., n` of the members. Any `t + 1` shares reconstruct the secret, while `t` or
+//! less shares reveal nothing about it. The dealer publishes a commitment to every coefficient
+//! of the polynomial, which allows each member to verify that its share is consistent with
+//! the shares of the other members.
+//!
+//! Two commitment schemes are supported:
+//!
+//! * [feldman]: commits to the coefficients with \\( g^{a_l} \\). Verification is cheaper, but
+//!   the commitments reveal \\( g^{secret} \\).
+//! * [pedersen]: commits to the coefficients with \\( g^{a_l}h^{b_l} \\), using the coefficients
+//!   of a second, hiding, polynomial. The commitments reveal nothing about the secret.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use curve25519_dalek::ristretto::RistrettoPoint;
+//! use curve25519_dalek::scalar::Scalar;
+//! use rand_core::OsRng;
+//! use DKG::vss::{feldman, reconstruct};
+//!
+//! let mut rng = OsRng;
+//! let secret = Scalar::random(&mut rng);
+//!
+//! // Back up the secret to 5 guardians, such that any 3 of them can recover it.
+//! let (dealing, shares) = feldman::Dealing::<RistrettoPoint>::new(&mut rng, 2, 5, &secret);
+//! assert!(shares.iter().all(|share| dealing.verify_share(share)));
+//!
+//! assert_eq!(reconstruct(2, &shares[1..4]), Ok(secret));
+//! ```
+//!
+//! [feldman]: crate::vss::feldman
+//! [pedersen]: crate::vss::pedersen
+
+pub mod feldman;
+pub mod pedersen;
+
+use crate::errors::DkgError;
+use crate::polynomial::lagrange_interpolation;
+use crate::traits::Scalar;
+
+/// Share of a secret, which is the evaluation of the sharing polynomial at the `index` of its
+/// holder.
+#[derive(Clone)]
+pub struct Share<S: Scalar> {
+    pub index: usize,
+    pub value: S,
+}
+
+impl<S: Scalar> std::fmt::Debug for Share<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Share")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Scalar> PartialEq for Share<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value.constant_time_eq(&other.value) && self.index == other.index
+    }
+}
+
+impl<S: Scalar> Eq for Share<S> {}
+
+impl<S: Scalar> Share<S> {
+    /// Debug representation of the share which, unlike `Debug`, includes the secret value.
+    /// Handle the output with care, as it discloses the share.
+    pub fn danger_debug(&self) -> String {
+        format!("Share {{ index: {}, value: {:?} }}", self.index, self.value)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<S: Scalar> zeroize::Zeroize for Share<S> {
+    fn zeroize(&mut self) {
+        self.value.erase();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<S: Scalar> Drop for Share<S> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+/// Reconstruct the secret shared with a polynomial of degree `threshold`, from the first
+/// `threshold + 1` shares of `shares`. The shares are not verified, which should be done
+/// beforehand with the `verify_share` function of the commitment scheme.
+///
+/// # Errors
+///
+/// Returns `DkgError::InsufficientShares` if there are less than `threshold + 1` shares, and
+/// `DkgError::InvalidShareIndex` if one of the used shares has index zero, or the same index
+/// as a previous share.
+pub fn reconstruct<S: Scalar>(threshold: usize, shares: &[Share<S>]) -> Result<S, DkgError> {
+    let shares = shares
+        .get(..threshold + 1)
+        .ok_or(DkgError::InsufficientShares(threshold + 1))?;
+
+    let indices = share_indices(shares.iter().map(|share| share.index))?;
+    let values: Vec<S> = shares.iter().map(|share| share.value).collect();
+
+    Ok(lagrange_interpolation(S::zero(), &values, &indices))
+}
+
+// Returns the indices as scalars, checking that they are non-zero and distinct.
+pub(crate) fn share_indices<S: Scalar>(
+    indices: impl Iterator<Item = usize>,
+) -> Result<Vec<S>, DkgError> {
+    let mut seen: Vec<usize> = Vec::new();
+    for index in indices {
+        if index == 0 || seen.contains(&index) {
+            return Err(DkgError::InvalidShareIndex(index));
+        }
+        seen.push(index);
+    }
+    Ok(seen.into_iter().map(|i| S::from_u64(i as u64)).collect())
+}
+
+// Returns the indices `1, .., nr_members` of the members as scalars.
+pub(crate) fn member_indices<S: Scalar>(nr_members: usize) -> Vec<S> {
+    (1..=nr_members).map(|i| S::from_u64(i as u64)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial::Polynomial;
+    use curve25519_dalek::scalar::Scalar as RScalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn reconstruction() {
+        let mut rng = OsRng;
+        let secret = RScalar::random(&mut rng);
+        let polynomial = Polynomial::random_with_secret(&mut rng, 2, secret);
+        let shares: Vec<Share<RScalar>> = [4usize, 1, 6, 2]
+            .iter()
+            .map(|&index| Share {
+                index,
+                value: polynomial.evaluate(&RScalar::from_u64(index as u64)),
+            })
+            .collect();
+
+        assert_eq!(reconstruct(2, &shares), Ok(secret));
+        assert_eq!(reconstruct(2, &shares[1..]), Ok(secret));
+        assert_eq!(
+            reconstruct(2, &shares[2..]),
+            Err(DkgError::InsufficientShares(3))
+        );
+
+        let mut duplicated = shares.clone();
+        duplicated[2].index = 4;
+        assert_eq!(
+            reconstruct(2, &duplicated),
+            Err(DkgError::InvalidShareIndex(4))
+        );
+        duplicated[2].index = 0;
+        assert_eq!(
+            reconstruct(2, &duplicated),
+            Err(DkgError::InvalidShareIndex(0))
+        );
+    }
+}
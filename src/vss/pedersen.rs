@@ -0,0 +1,278 @@
+//! Pedersen VSS. The dealer samples a sharing polynomial, \\( f(x) = \sum_{l = 0}^t a_l x^l \\),
+//! and a hiding polynomial, \\( f'(x) = \sum_{l = 0}^t b_l x^l \\), and commits to their
+//! coefficients with \\( E_l = g^{a_l}h^{b_l} \\). Member \\( i \\) receives
+//! \\( s_i = f(i) \\) and \\( s_i' = f'(i) \\), and verifies them by checking that
+//! \\( g^{s_i}h^{s_i'} = \prod_{l = 0}^t E_l^{i^l}. \\)
+//!
+//! Unlike Feldman VSS, the commitments reveal nothing about the secret.
+use super::member_indices;
+use crate::cryptography::commitment::CommitmentKey;
+use crate::errors::DkgError;
+use crate::polynomial::Polynomial;
+use crate::traits::{PrimeGroupElement, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+/// Share of a Pedersen dealing, the evaluations at `index` of the sharing polynomial,
+/// `value`, and of the hiding polynomial, `randomness`.
+#[derive(Clone)]
+pub struct Share<S: Scalar> {
+    pub index: usize,
+    pub value: S,
+    pub randomness: S,
+}
+
+impl<S: Scalar> std::fmt::Debug for Share<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Share")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Scalar> PartialEq for Share<S> {
+    fn eq(&self, other: &Self) -> bool {
+        // Evaluate both secret comparisons, to not leak which one differs.
+        let equal_value = self.value.constant_time_eq(&other.value);
+        let equal_randomness = self.randomness.constant_time_eq(&other.randomness);
+        equal_value & equal_randomness && self.index == other.index
+    }
+}
+
+impl<S: Scalar> Eq for Share<S> {}
+
+impl<S: Scalar> Share<S> {
+    /// Debug representation of the share which, unlike `Debug`, includes the secret values.
+    /// Handle the output with care, as it discloses the share.
+    pub fn danger_debug(&self) -> String {
+        format!(
+            "Share {{ index: {}, value: {:?}, randomness: {:?} }}",
+            self.index, self.value, self.randomness
+        )
+    }
+
+    /// Share of the secret, without the randomness.
+    pub fn to_share(&self) -> super::Share<S> {
+        super::Share {
+            index: self.index,
+            value: self.value,
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<S: Scalar> zeroize::Zeroize for Share<S> {
+    fn zeroize(&mut self) {
+        self.value.erase();
+        self.randomness.erase();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<S: Scalar> Drop for Share<S> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+/// Public part of a Pedersen dealing, the commitments to the coefficients of the sharing
+/// polynomial, starting from the constant term.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dealing<G: PrimeGroupElement> {
+    commitments: Vec<G>,
+}
+
+impl<G: PrimeGroupElement> Dealing<G> {
+    /// Share `secret` among `nr_members` members, such that `threshold + 1` shares are
+    /// required to reconstruct it. Returns the dealing, and the shares of the members, where
+    /// the share at position `i - 1` belongs to member `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is not smaller than `nr_members`.
+    pub fn new<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        commitment_key: &CommitmentKey<G>,
+        threshold: usize,
+        nr_members: usize,
+        secret: &G::CorrespondingScalar,
+    ) -> (Self, Vec<Share<G::CorrespondingScalar>>) {
+        assert!(threshold < nr_members);
+        let hiding_polynomial = Polynomial::random(rng, threshold);
+        let sharing_polynomial = Polynomial::random_with_secret(rng, threshold, *secret);
+
+        let indices = member_indices(nr_members);
+        let shares = sharing_polynomial
+            .evaluate_many(&indices)
+            .into_iter()
+            .zip(hiding_polynomial.evaluate_many(&indices))
+            .enumerate()
+            .map(|(i, (value, randomness))| Share {
+                index: i + 1,
+                value,
+                randomness,
+            })
+            .collect();
+
+        (
+            Self::commit(commitment_key, &sharing_polynomial, &hiding_polynomial),
+            shares,
+        )
+    }
+
+    /// Commit to the coefficients of `sharing_polynomial`, using the coefficients of
+    /// `hiding_polynomial` as randomness.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both polynomials do not have the same capacity.
+    pub fn commit(
+        commitment_key: &CommitmentKey<G>,
+        sharing_polynomial: &Polynomial<G::CorrespondingScalar>,
+        hiding_polynomial: &Polynomial<G::CorrespondingScalar>,
+    ) -> Self {
+        assert_eq!(sharing_polynomial.capacity(), hiding_polynomial.capacity());
+        Dealing {
+            commitments: sharing_polynomial
+                .get_coefficients()
+                .zip(hiding_polynomial.get_coefficients())
+                .map(|(a, b)| commitment_key.commit_with_random(a, b))
+                .collect(),
+        }
+    }
+
+    /// Initialise a dealing of a polynomial of degree `threshold` from the commitments
+    /// published by the dealer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DkgError::InvalidNumberOfCommitments` if there are not exactly
+    /// `threshold + 1` commitments, as a polynomial of larger degree cannot be reconstructed
+    /// from `threshold + 1` shares.
+    pub fn from_commitments(threshold: usize, commitments: Vec<G>) -> Result<Self, DkgError> {
+        if commitments.len() != threshold + 1 {
+            return Err(DkgError::InvalidNumberOfCommitments(threshold + 1));
+        }
+        Ok(Dealing { commitments })
+    }
+
+    pub fn commitments(&self) -> &[G] {
+        &self.commitments
+    }
+
+    pub fn into_commitments(self) -> Vec<G> {
+        self.commitments
+    }
+
+    /// Verify that `share` is consistent with the commitments of the dealing.
+    pub fn verify_share(
+        &self,
+        commitment_key: &CommitmentKey<G>,
+        share: &Share<G::CorrespondingScalar>,
+    ) -> bool {
+        check_share(commitment_key, &self.commitments, share)
+    }
+}
+
+/// Verify that `share` is consistent with the `commitments` to the coefficients of the
+/// sharing polynomial of degree `threshold`, i.e.
+/// \\( g^{s_i}h^{s_i'} = \prod_{l = 0}^t E_l^{i^l}. \\) Returns false if there are not
+/// exactly `threshold + 1` commitments.
+pub fn verify_share<G: PrimeGroupElement>(
+    commitment_key: &CommitmentKey<G>,
+    threshold: usize,
+    commitments: &[G],
+    share: &Share<G::CorrespondingScalar>,
+) -> bool {
+    commitments.len() == threshold + 1 && check_share(commitment_key, commitments, share)
+}
+
+// Check of the share against the commitments, whose length is the number of coefficients.
+fn check_share<G: PrimeGroupElement>(
+    commitment_key: &CommitmentKey<G>,
+    commitments: &[G],
+    share: &Share<G::CorrespondingScalar>,
+) -> bool {
+    let index_pow = <G::CorrespondingScalar as Scalar>::from_u64(share.index as u64)
+        .exp_iter()
+        .take(commitments.len());
+
+    let check_element = commitment_key.commit_with_random(&share.value, &share.randomness);
+    let multi_scalar =
+        G::vartime_multiscalar_multiplication(index_pow, commitments.iter().copied());
+
+    check_element == multi_scalar
+}
+
+/// Reconstruct the secret of a Pedersen dealing from the first `threshold + 1` shares of
+/// `shares`. See `vss::reconstruct`.
+pub fn reconstruct<S: Scalar>(threshold: usize, shares: &[Share<S>]) -> Result<S, DkgError> {
+    let shares: Vec<super::Share<S>> = shares.iter().map(Share::to_share).collect();
+    super::reconstruct(threshold, &shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar as RScalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn pedersen_dealing() {
+        let mut rng = OsRng;
+        let commitment_key = CommitmentKey::<RistrettoPoint>::generate(&[0u8]);
+        let secret = RScalar::random(&mut rng);
+        let (dealing, mut shares) =
+            Dealing::<RistrettoPoint>::new(&mut rng, &commitment_key, 2, 5, &secret);
+
+        assert_eq!(dealing.commitments().len(), 3);
+        assert!(shares
+            .iter()
+            .all(|share| dealing.verify_share(&commitment_key, share)));
+        assert_eq!(reconstruct(2, &shares[..3]), Ok(secret));
+        assert_eq!(
+            reconstruct(2, &shares[3..]),
+            Err(DkgError::InsufficientShares(3))
+        );
+
+        // A share with a different value or randomness does not verify.
+        shares[0].value += RScalar::one();
+        assert!(!dealing.verify_share(&commitment_key, &shares[0]));
+        shares[1].randomness += RScalar::one();
+        assert!(!dealing.verify_share(&commitment_key, &shares[1]));
+
+        let other_key = CommitmentKey::<RistrettoPoint>::generate(&[1u8]);
+        assert!(!dealing.verify_share(&other_key, &shares[2]));
+
+        let received = Dealing::from_commitments(2, dealing.commitments().to_vec()).unwrap();
+        assert_eq!(received, dealing);
+        assert!(verify_share(
+            &commitment_key,
+            2,
+            dealing.commitments(),
+            &shares[2]
+        ));
+    }
+
+    #[test]
+    fn larger_degree_dealing() {
+        let mut rng = OsRng;
+        let commitment_key = CommitmentKey::<RistrettoPoint>::generate(&[0u8]);
+        let secret = RScalar::random(&mut rng);
+        let (dealing, shares) =
+            Dealing::<RistrettoPoint>::new(&mut rng, &commitment_key, 3, 5, &secret);
+        assert!(dealing.verify_share(&commitment_key, &shares[0]));
+        assert_ne!(reconstruct(2, &shares), Ok(secret));
+
+        assert!(!verify_share(
+            &commitment_key,
+            2,
+            dealing.commitments(),
+            &shares[0]
+        ));
+        assert_eq!(
+            Dealing::from_commitments(2, dealing.into_commitments()),
+            Err(DkgError::InvalidNumberOfCommitments(3))
+        );
+    }
+}